@@ -5,19 +5,35 @@ use std::str::FromStr;
 use std::time::Duration;
 
 use borsh::{BorshDeserialize, BorshSerialize};
+use ed25519_dalek::{
+    PublicKey as Ed25519PublicKey, Signature as Ed25519Signature, Verifier,
+};
 use ibc::ics02_client::client_consensus::AnyConsensusState;
 use ibc::ics02_client::client_state::AnyClientState;
 use ibc::ics02_client::header::AnyHeader;
 use ibc::ics02_client::height::Height;
 use ibc::ics03_connection::connection::Counterparty;
 use ibc::ics03_connection::version::Version;
-use ibc::ics23_commitment::commitment::CommitmentProofBytes;
+use ibc::ics04_channel::channel::ChannelEnd;
+use ibc::ics04_channel::packet::{Packet, Sequence};
+use ibc::ics04_channel::version::Version as ChannelVersion;
+use ibc::ics23_commitment::commitment::{CommitmentProofBytes, CommitmentRoot};
+use ibc::ics23_commitment::specs::ProofSpecs;
 use ibc::proofs::{ConsensusProof, Proofs};
-use ibc::ics24_host::identifier::{ClientId, ConnectionId};
+use ibc::ics24_host::identifier::{
+    ChannelId, ClientId, ConnectionId, PortId,
+};
+use ibc::timestamp::Timestamp;
 use ibc_proto::ibc::core::commitment::v1::MerkleProof;
 use ibc_proto::ibc::core::connection::v1::Counterparty as RawCounterparty;
+use ics23::commitment_proof::Proof as Ics23Proof;
+use ics23::{
+    CommitmentProof, ExistenceProof, HashOp, InnerOp, LeafOp, LengthOp,
+    NonExistenceProof, ProofSpec,
+};
 use prost::Message;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use tendermint_proto::Protobuf;
 use thiserror::Error;
 
@@ -26,384 +42,4039 @@ use thiserror::Error;
 pub enum Error {
     #[error("Decoding error: {0}")]
     DecodingError(String),
+    #[error("Proof verification error: {0}")]
+    ProofVerificationFailure(String),
+    #[error("Invalid client state: {0}")]
+    InvalidClientState(String),
+    #[error("Invalid counterparty: {0}")]
+    InvalidCounterparty(String),
+    #[error("Invalid version: {0}")]
+    InvalidVersion(String),
+    #[error("Invalid proof: {0}")]
+    InvalidProof(String),
+    #[error("Invalid channel end: {0}")]
+    InvalidChannel(String),
+    #[error("Invalid packet: {0}")]
+    InvalidPacket(String),
 }
 
 /// Decode result for IBC data
 pub type Result<T> = std::result::Result<T, Error>;
 
-/// States to create a new client
+/// A solo-machine (ICS06) consensus state: the device's current public
+/// key, a diversifier distinguishing its signing domain from other chains
+/// using the same key, and the timestamp of the last update.
 #[derive(
     Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
 )]
-pub struct ClientCreationData {
-    /// The client state
-    client_state: Vec<u8>,
-    /// The consensus state
-    consensus_state: Vec<u8>,
+pub struct SoloMachineConsensusState {
+    public_key: Vec<u8>,
+    diversifier: String,
+    timestamp: u64,
 }
 
-impl ClientCreationData {
-    /// Returns the data to create a new client
+impl SoloMachineConsensusState {
+    /// Returns a new solo-machine consensus state
     pub fn new(
-        client_state: AnyClientState,
-        consensus_state: AnyConsensusState,
+        public_key: Vec<u8>,
+        diversifier: String,
+        timestamp: u64,
     ) -> Self {
-        let client_state = client_state
-            .encode_vec()
-            .expect("Encoding a client state shouldn't fail");
-        let consensus_state = consensus_state
-            .encode_vec()
-            .expect("Encoding a consensus state shouldn't fail");
         Self {
-            client_state,
-            consensus_state,
+            public_key,
+            diversifier,
+            timestamp,
         }
     }
 
-    /// Returns the client state
-    pub fn client_state(&self) -> Result<AnyClientState> {
-        AnyClientState::decode_vec(&self.client_state)
-            .map_err(|e| Error::DecodingError(e.to_string()))
+    /// Returns the device's public key
+    pub fn public_key(&self) -> &[u8] {
+        &self.public_key
     }
 
-    /// Returns the consensus state
-    pub fn consensus_state(&self) -> Result<AnyConsensusState> {
-        AnyConsensusState::decode_vec(&self.consensus_state)
-            .map_err(|e| Error::DecodingError(e.to_string()))
+    /// Returns the diversifier
+    pub fn diversifier(&self) -> &str {
+        &self.diversifier
+    }
+
+    /// Returns the timestamp of the last update
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
     }
 }
 
-/// Data to update a client
+/// A solo-machine (ICS06) client state: the current sequence, whether the
+/// client has been frozen by a misbehaviour submission, and the latest
+/// consensus state.
 #[derive(
     Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
 )]
-pub struct ClientUpdateData {
-    /// The updated client ID
-    client_id: String,
-    /// The headers to update the client
-    headers: Vec<Vec<u8>>,
+pub struct SoloMachineClientState {
+    sequence: u64,
+    is_frozen: bool,
+    consensus_state: SoloMachineConsensusState,
 }
 
-impl ClientUpdateData {
-    /// Returns the data to update a client
-    pub fn new(client_id: ClientId, headers: Vec<AnyHeader>) -> Self {
-        let client_id = client_id.as_str().to_owned();
-        let headers = headers
-            .iter()
-            .map(|h| {
-                h.encode_vec()
-                    .expect("Encoding a client header shouldn't fail")
-            })
-            .collect();
-        Self { client_id, headers }
+impl SoloMachineClientState {
+    /// Returns a new solo-machine client state
+    pub fn new(
+        sequence: u64,
+        is_frozen: bool,
+        consensus_state: SoloMachineConsensusState,
+    ) -> Self {
+        Self {
+            sequence,
+            is_frozen,
+            consensus_state,
+        }
     }
 
-    /// Returns the client ID
-    pub fn client_id(&self) -> Result<ClientId> {
-        ClientId::from_str(&self.client_id)
-            .map_err(|e| Error::DecodingError(e.to_string()))
+    /// Returns the current sequence
+    pub fn sequence(&self) -> u64 {
+        self.sequence
     }
 
-    /// Returns the header
-    pub fn headers(&self) -> Result<Vec<AnyHeader>> {
-        let mut headers = vec![];
-        for h in &self.headers {
-            let header = AnyHeader::decode_vec(h)
-                .map_err(|e| Error::DecodingError(e.to_string()))?;
-            headers.push(header);
+    /// Returns whether the client has been frozen
+    pub fn is_frozen(&self) -> bool {
+        self.is_frozen
+    }
+
+    /// Returns the latest consensus state
+    pub fn consensus_state(&self) -> &SoloMachineConsensusState {
+        &self.consensus_state
+    }
+
+    /// Returns the client state to store after a signature at the current
+    /// sequence has been verified: the sequence bumps by one, as ICS06
+    /// requires to guard against replay.
+    pub fn next(&self) -> Self {
+        Self {
+            sequence: self.sequence + 1,
+            is_frozen: self.is_frozen,
+            consensus_state: self.consensus_state.clone(),
         }
-        Ok(headers)
     }
 }
 
-/// Data to upgrade a client
+/// A solo-machine (ICS06) header: it advances the client's sequence by
+/// rotating in a new public key and diversifier, authorized by a
+/// signature from the previous key.
 #[derive(
     Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
 )]
-pub struct ClientUpgradeData {
-    /// The upgraded client ID
-    client_id: String,
-    /// The client state
-    client_state: Vec<u8>,
-    /// The consensus state
-    consensus_state: Vec<u8>,
-    /// The proof of the client state
-    proof_client: Vec<u8>,
-    /// The proof of the consensus state
-    proof_consensus_state: Vec<u8>,
+pub struct SoloMachineHeader {
+    sequence: u64,
+    timestamp: u64,
+    new_public_key: Vec<u8>,
+    new_diversifier: String,
+    signature: Vec<u8>,
 }
 
-impl ClientUpgradeData {
-    /// Returns the data to upgrade a client
+impl SoloMachineHeader {
+    /// Returns a new solo-machine header
     pub fn new(
-        client_id: ClientId,
-        client_state: AnyClientState,
-        consensus_state: AnyConsensusState,
-        client_proof: MerkleProof,
-        consensus_proof: MerkleProof,
+        sequence: u64,
+        timestamp: u64,
+        new_public_key: Vec<u8>,
+        new_diversifier: String,
+        signature: Vec<u8>,
     ) -> Self {
-        let client_id = client_id.as_str().to_owned();
-        let client_state = client_state
-            .encode_vec()
-            .expect("Encoding a client state shouldn't fail");
-        let consensus_state = consensus_state
-            .encode_vec()
-            .expect("Encoding a consensus state shouldn't fail");
-        let mut proof_client = vec![];
-        client_proof
-            .encode(&mut proof_client)
-            .expect("Encoding a client proof shouldn't fail");
-        let mut proof_consensus_state = vec![];
-        consensus_proof
-            .encode(&mut proof_consensus_state)
-            .expect("Encoding a consensus proof shouldn't fail");
         Self {
-            client_id,
-            client_state,
-            consensus_state,
-            proof_client,
-            proof_consensus_state,
+            sequence,
+            timestamp,
+            new_public_key,
+            new_diversifier,
+            signature,
         }
     }
 
-    /// Returns the client ID
-    pub fn client_id(&self) -> Result<ClientId> {
-        ClientId::from_str(&self.client_id)
-            .map_err(|e| Error::DecodingError(e.to_string()))
+    /// Returns the sequence this header updates from
+    pub fn sequence(&self) -> u64 {
+        self.sequence
     }
 
-    /// Returns the client state
-    pub fn client_state(&self) -> Result<AnyClientState> {
-        AnyClientState::decode_vec(&self.client_state)
-            .map_err(|e| Error::DecodingError(e.to_string()))
+    /// Returns the timestamp of the update
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
     }
 
-    /// Returns the consensus state
-    pub fn consensus_state(&self) -> Result<AnyConsensusState> {
-        AnyConsensusState::decode_vec(&self.consensus_state)
-            .map_err(|e| Error::DecodingError(e.to_string()))
+    /// Returns the rotated-in public key
+    pub fn new_public_key(&self) -> &[u8] {
+        &self.new_public_key
     }
 
-    /// Returns the proof for client state
-    pub fn proof_client(&self) -> Result<MerkleProof> {
-        MerkleProof::decode(&self.proof_client[..])
-            .map_err(|e| Error::DecodingError(e.to_string()))
+    /// Returns the rotated-in diversifier
+    pub fn new_diversifier(&self) -> &str {
+        &self.new_diversifier
     }
 
-    /// Returns the proof for consensus state
-    pub fn proof_consensus_state(&self) -> Result<MerkleProof> {
-        MerkleProof::decode(&self.proof_consensus_state[..])
-            .map_err(|e| Error::DecodingError(e.to_string()))
+    /// Returns the signature authorizing the rotation, made with the
+    /// previous public key
+    pub fn signature(&self) -> &[u8] {
+        &self.signature
     }
 }
 
-/// Data to initialize a connection
+/// The data a solo machine signs to authenticate a value at a given path
+/// and sequence, as specified by ICS06. This mirrors the layout of
+/// ICS06's `SignBytes` protobuf message: real solo-machine signers sign
+/// over the protobuf encoding, not an internal serialization format.
+#[derive(Clone, PartialEq, ::prost::Message)]
+struct SignBytes {
+    #[prost(uint64, tag = "1")]
+    sequence: u64,
+    #[prost(uint64, tag = "2")]
+    timestamp: u64,
+    #[prost(string, tag = "3")]
+    diversifier: String,
+    #[prost(bytes, tag = "4")]
+    path: Vec<u8>,
+    #[prost(bytes, tag = "5")]
+    data: Vec<u8>,
+}
+
+/// Verifies that `signature` was produced by the solo machine's current
+/// public key over the given `path`/`data` at `client_state`'s current
+/// sequence, by reconstructing the canonical [`SignBytes`] and checking
+/// the signature against the key stored in `client_state`'s consensus
+/// state. Returns the client state to store next: the sequence bumps by
+/// one, as ICS06 requires to guard against replay.
+pub fn verify_signature(
+    client_state: &SoloMachineClientState,
+    timestamp: u64,
+    path: &[u8],
+    data: &[u8],
+    signature: &[u8],
+) -> Result<SoloMachineClientState> {
+    if client_state.is_frozen() {
+        return Err(Error::ProofVerificationFailure(
+            "the solo machine client is frozen".to_owned(),
+        ));
+    }
+    let consensus_state = client_state.consensus_state();
+    let sign_bytes = SignBytes {
+        sequence: client_state.sequence(),
+        timestamp,
+        diversifier: consensus_state.diversifier().to_owned(),
+        path: path.to_vec(),
+        data: data.to_vec(),
+    };
+    let mut bytes = vec![];
+    sign_bytes
+        .encode(&mut bytes)
+        .expect("Encoding sign bytes shouldn't fail");
+    let public_key = Ed25519PublicKey::from_bytes(consensus_state.public_key())
+        .map_err(|e| Error::ProofVerificationFailure(e.to_string()))?;
+    let signature = Ed25519Signature::from_bytes(signature)
+        .map_err(|e| Error::ProofVerificationFailure(e.to_string()))?;
+    public_key
+        .verify(&bytes, &signature)
+        .map_err(|e| Error::ProofVerificationFailure(e.to_string()))?;
+    Ok(client_state.next())
+}
+
+/// The rotated-in public key and diversifier a solo-machine header signs,
+/// as specified by ICS06's `HeaderData` protobuf message.
+#[derive(Clone, PartialEq, ::prost::Message)]
+struct HeaderData {
+    #[prost(bytes, tag = "1")]
+    new_public_key: Vec<u8>,
+    #[prost(string, tag = "2")]
+    new_diversifier: String,
+}
+
+/// Verifies that `header`'s rotation signature was produced by
+/// `client_state`'s *current* public key, by reconstructing the
+/// canonical [`SignBytes`] over the protobuf-encoded [`HeaderData`] it
+/// authorizes (the same `SignBytes` envelope [`verify_signature`] uses to
+/// authenticate a value, with an empty path since a header update isn't
+/// anchored to one), and checking it against the key stored in
+/// `client_state`'s consensus state. Returns the client state and
+/// consensus state to store next: the sequence bumps by one, as ICS06
+/// requires to guard against replay, and the public key/diversifier
+/// rotate to the ones `header` carries.
+pub fn apply_header(
+    client_state: &SoloMachineClientState,
+    header: &SoloMachineHeader,
+) -> Result<(SoloMachineClientState, SoloMachineConsensusState)> {
+    if client_state.is_frozen() {
+        return Err(Error::ProofVerificationFailure(
+            "the solo machine client is frozen".to_owned(),
+        ));
+    }
+    if header.sequence() != client_state.sequence() {
+        return Err(Error::ProofVerificationFailure(
+            "the header's sequence doesn't match the client's current \
+             sequence"
+                .to_owned(),
+        ));
+    }
+    let consensus_state = client_state.consensus_state();
+    let header_data = HeaderData {
+        new_public_key: header.new_public_key().to_vec(),
+        new_diversifier: header.new_diversifier().to_owned(),
+    };
+    let mut data = vec![];
+    header_data
+        .encode(&mut data)
+        .expect("Encoding header data shouldn't fail");
+    let sign_bytes = SignBytes {
+        sequence: client_state.sequence(),
+        timestamp: header.timestamp(),
+        diversifier: consensus_state.diversifier().to_owned(),
+        path: vec![],
+        data,
+    };
+    let mut bytes = vec![];
+    sign_bytes
+        .encode(&mut bytes)
+        .expect("Encoding sign bytes shouldn't fail");
+    let public_key = Ed25519PublicKey::from_bytes(consensus_state.public_key())
+        .map_err(|e| Error::ProofVerificationFailure(e.to_string()))?;
+    let signature = Ed25519Signature::from_bytes(header.signature())
+        .map_err(|e| Error::ProofVerificationFailure(e.to_string()))?;
+    public_key
+        .verify(&bytes, &signature)
+        .map_err(|e| Error::ProofVerificationFailure(e.to_string()))?;
+
+    let next_consensus_state = SoloMachineConsensusState::new(
+        header.new_public_key().to_vec(),
+        header.new_diversifier().to_owned(),
+        header.timestamp(),
+    );
+    let next_client_state = SoloMachineClientState::new(
+        client_state.sequence() + 1,
+        client_state.is_frozen(),
+        next_consensus_state.clone(),
+    );
+    Ok((next_client_state, next_consensus_state))
+}
+
+/// The size, in bytes, of a compressed BLS12-381 public key.
+const BLS_PUBLIC_KEY_BYTES: usize = 48;
+/// The number of validators in an Ethereum Altair sync committee.
+const SYNC_COMMITTEE_SIZE: usize = 512;
+/// The generalized index of `next_sync_committee` within a Beacon state,
+/// per the Altair light-client sync protocol.
+const NEXT_SYNC_COMMITTEE_GENERALIZED_INDEX: u64 = 55;
+/// The generalized index of `finalized_checkpoint.root` within a Beacon
+/// state.
+const FINALIZED_ROOT_GENERALIZED_INDEX: u64 = 105;
+/// The `DOMAIN_SYNC_COMMITTEE` domain type, per the Altair spec.
+const SYNC_COMMITTEE_DOMAIN_TYPE: [u8; 4] = [0x07, 0x00, 0x00, 0x00];
+
+/// A minimal SSZ beacon block header: enough fields to anchor a light
+/// client at a slot and to verify Merkle branches against its state root.
 #[derive(
     Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
 )]
-pub struct ConnectionOpenInitData {
-    /// The corresponding client ID
-    client_id: String,
-    /// The corresponding counterparty
-    counterparty: Vec<u8>,
-    /// The version
-    version: Vec<u8>,
-    /// The delay period as (secs, nanos)
-    delay_period: (u64, u32),
+pub struct BeaconBlockHeader {
+    slot: u64,
+    proposer_index: u64,
+    parent_root: Vec<u8>,
+    state_root: Vec<u8>,
+    body_root: Vec<u8>,
 }
 
-impl ConnectionOpenInitData {
-    /// Returns the data to initalize a connection
+impl BeaconBlockHeader {
+    /// Returns a new beacon block header
     pub fn new(
-        client_id: ClientId,
-        counterparty: Counterparty,
-        version: Version,
-        delay_period: Duration,
+        slot: u64,
+        proposer_index: u64,
+        parent_root: Vec<u8>,
+        state_root: Vec<u8>,
+        body_root: Vec<u8>,
     ) -> Self {
-        let client_id = client_id.as_str().to_owned();
-        // TODO: Need Profobuf implementation for Counterparty in ibc-rs
-        // let counterparty = counterparty.encode_vec().expect("Encoding a
-        // counterparty shouldn't fail");
-        let mut bytes = vec![];
-        RawCounterparty::from(counterparty)
-            .encode(&mut bytes)
-            .expect("Encoding a counterparty shouldn't fail");
-        let version = version
-            .encode_vec()
-            .expect("Encoding a version shouldn't fail");
         Self {
-            client_id,
-            counterparty: bytes,
-            version,
-            delay_period: (delay_period.as_secs(), delay_period.subsec_nanos()),
+            slot,
+            proposer_index,
+            parent_root,
+            state_root,
+            body_root,
         }
     }
 
-    /// Returns the client ID
-    pub fn client_id(&self) -> Option<ClientId> {
-        ClientId::from_str(&self.client_id).ok()
-    }
-
-    /// Returns the counterparty
-    pub fn counterparty(&self) -> Option<Counterparty> {
-        // TODO: Need Profobuf implementation for Counterparty in ibc-rs
-        // Counterparty::decode_vec(self.counterparty).ok()
-        match RawCounterparty::decode(&self.counterparty[..]) {
-            Ok(c) => c.try_into().ok(),
-            Err(_) => None,
-        }
+    /// Returns the slot of this header
+    pub fn slot(&self) -> u64 {
+        self.slot
     }
 
-    /// Returns the version
-    pub fn version(&self) -> Option<Version> {
-        Version::decode_vec(&self.version).ok()
+    /// Returns the state root committed to by this header
+    pub fn state_root(&self) -> &[u8] {
+        &self.state_root
     }
 
-    /// Returns the delay period
-    pub fn delay_period(&self) -> Duration {
-        Duration::new(self.delay_period.0, self.delay_period.1)
+    /// Computes the SSZ `hash_tree_root` of this header
+    pub fn hash_tree_root(&self) -> Result<[u8; 32]> {
+        let mut slot_chunk = [0u8; 32];
+        slot_chunk[..8].copy_from_slice(&self.slot.to_le_bytes());
+        let mut proposer_chunk = [0u8; 32];
+        proposer_chunk[..8]
+            .copy_from_slice(&self.proposer_index.to_le_bytes());
+        let chunks = [
+            slot_chunk,
+            proposer_chunk,
+            as_chunk(&self.parent_root)?,
+            as_chunk(&self.state_root)?,
+            as_chunk(&self.body_root)?,
+        ];
+        Ok(ssz_merkleize(&chunks, chunks.len()))
     }
 }
 
-/// Data to try to open a connection
+/// An Ethereum Altair sync-committee-based light client's consensus
+/// state: the last finalized beacon header and the sync committee that
+/// was active at that time.
 #[derive(
     Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
 )]
-pub struct ConnectionOpenTryData {
-    prev_conn_id: Option<String>,
-    client_id: String,
-    client_state: Vec<u8>,
-    counterparty: Vec<u8>,
-    counterparty_versions: Vec<Vec<u8>>,
-    proof_height: (u64, u64),
-    proof_connection: Vec<u8>,
-    proof_client: Vec<u8>,
-    proof_consensus: Vec<u8>,
-    delay_period: (u64, u32),
+pub struct EthBeaconConsensusState {
+    finalized_header: BeaconBlockHeader,
+    current_sync_committee: Vec<Vec<u8>>,
+    current_sync_committee_aggregate: Vec<u8>,
 }
 
-impl ConnectionOpenTryData {
-    /// Returns the data to try to open a connection
+impl EthBeaconConsensusState {
+    /// Returns a new Ethereum beacon-chain consensus state
     pub fn new(
-        prev_conn_id: Option<ConnectionId>,
-        client_id: ClientId,
-        client_state: AnyClientState,
-        counterparty: Counterparty,
-        counterparty_versions: Vec<Version>,
-        proof_height: Height,
-        proof_connection: CommitmentProofBytes,
-        proof_client: CommitmentProofBytes,
-        proof_consensus: CommitmentProofBytes,
-        delay_period: Duration,
+        finalized_header: BeaconBlockHeader,
+        current_sync_committee: Vec<Vec<u8>>,
+        current_sync_committee_aggregate: Vec<u8>,
     ) -> Self {
-        let prev_conn_id = prev_conn_id.map(|id| id.as_str().to_owned());
-        let client_id = client_id.as_str().to_owned();
-        let client_state = client_state
-            .encode_vec()
-            .expect("Encoding a client state shouldn't fail");
-        // TODO: Need Profobuf implementation for Counterparty in ibc-rs
-        // let counterparty = counterparty.encode_vec().expect("Encoding a
-        // counterparty shouldn't fail");
-        let mut bytes = vec![];
-        RawCounterparty::from(counterparty)
-            .encode(&mut bytes)
-            .expect("Encoding a counterparty shouldn't fail");
-        let versions = counterparty_versions
-            .iter()
-            .map(|v| v.encode_vec().expect("Encoding a version shouldn't fail"))
-            .collect();
         Self {
-            prev_conn_id,
-            client_id,
-            client_state,
-            counterparty: bytes,
-            counterparty_versions: versions,
-            proof_height: (
-                proof_height.revision_number,
-                proof_height.revision_height,
-            ),
-            proof_connection: proof_connection.into(),
-            proof_client: proof_client.into(),
-            proof_consensus: proof_consensus.into(),
-            delay_period: (delay_period.as_secs(), delay_period.subsec_nanos()),
+            finalized_header,
+            current_sync_committee,
+            current_sync_committee_aggregate,
         }
     }
 
-    /// Returns the previous connection ID
-    pub fn previous_connection_id(&self) -> Option<ConnectionId> {
-        match &self.prev_conn_id {
-            Some(id) => ConnectionId::from_str(id).ok(),
-            None => None,
-        }
+    /// Returns the last finalized beacon header
+    pub fn finalized_header(&self) -> &BeaconBlockHeader {
+        &self.finalized_header
     }
 
-    /// Returns the client ID
-    pub fn client_id(&self) -> Option<ClientId> {
-        ClientId::from_str(&self.client_id).ok()
+    /// Returns the currently active sync committee's public keys
+    pub fn current_sync_committee(&self) -> &[Vec<u8>] {
+        &self.current_sync_committee
     }
+}
 
-    /// Returns the client state
-    pub fn client_state(&self) -> Option<AnyClientState> {
-        AnyClientState::decode_vec(&self.client_state).ok()
-    }
+/// An Ethereum Altair client state: the genesis validators root (mixed
+/// into the signing domain) and whether the client has been frozen.
+#[derive(
+    Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct EthBeaconClientState {
+    genesis_validators_root: Vec<u8>,
+    is_frozen: bool,
+}
 
-    /// Returns the counterparty
-    pub fn counterparty(&self) -> Option<Counterparty> {
-        // TODO: Need Profobuf implementation for Counterparty in ibc-rs
-        // Counterparty::decode_vec(self.counterparty).ok()
-        match RawCounterparty::decode(&self.counterparty[..]) {
-            Ok(c) => c.try_into().ok(),
-            Err(_) => None,
+impl EthBeaconClientState {
+    /// Returns a new Ethereum beacon-chain client state
+    pub fn new(genesis_validators_root: Vec<u8>, is_frozen: bool) -> Self {
+        Self {
+            genesis_validators_root,
+            is_frozen,
         }
     }
 
-    /// Returns the list of versions
-    pub fn counterparty_versions(&self) -> Vec<Version> {
-        let mut versions = vec![];
-        for v in self.counterparty_versions {
-            match Version::decode_vec(&v) {
-                Ok(v) => versions.push(v),
-                Err(_) => return vec![],
-            }
-        }
-        versions
+    /// Returns the genesis validators root
+    pub fn genesis_validators_root(&self) -> &[u8] {
+        &self.genesis_validators_root
     }
 
-    /// Returns the height of the proofs
-    pub fn proof_height(&self) -> Height {
-        Height::new(self.proof_height.0, self.proof_height.1)
+    /// Returns whether the client has been frozen
+    pub fn is_frozen(&self) -> bool {
+        self.is_frozen
     }
+}
 
-    /// Returns the proof for connection
-    pub fn proof_connection(&self) -> CommitmentProofBytes {
-        self.proof_connection.into()
-    }
+/// The aggregate BLS signature of an Altair sync committee update: a
+/// bitvector of which of the 512 committee members participated, and
+/// their aggregated signature.
+#[derive(
+    Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct SyncAggregate {
+    sync_committee_bits: Vec<u8>,
+    sync_committee_signature: Vec<u8>,
+}
 
-    /// Returns the proof for client state
-    pub fn proof_client(&self) -> CommitmentProofBytes {
-        self.proof_client.into()
+impl SyncAggregate {
+    /// Returns a new sync aggregate
+    pub fn new(
+        sync_committee_bits: Vec<u8>,
+        sync_committee_signature: Vec<u8>,
+    ) -> Self {
+        Self {
+            sync_committee_bits,
+            sync_committee_signature,
+        }
     }
 
-    /// Returns the proof for consensus state
-    pub fn proof_consensus(&self) -> CommitmentProofBytes {
-        self.proof_consensus.into()
+    /// Returns the public keys of the committee members who participated,
+    /// given the committee they were drawn from
+    pub fn participants(
+        &self,
+        committee: &[Vec<u8>],
+    ) -> Result<Vec<Vec<u8>>> {
+        if committee.len() != SYNC_COMMITTEE_SIZE
+            || self.sync_committee_bits.len() * 8 < SYNC_COMMITTEE_SIZE
+        {
+            return Err(Error::ProofVerificationFailure(
+                "the sync committee or its participation bitvector has \
+                 the wrong size"
+                    .to_owned(),
+            ));
+        }
+        Ok(committee
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| {
+                let byte = self.sync_committee_bits[i / 8];
+                (byte >> (i % 8)) & 1 == 1
+            })
+            .map(|(_, pk)| pk.clone())
+            .collect())
     }
 
-    /// Returns the delay period
-    pub fn delay_period(&self) -> Duration {
-        Duration::new(self.delay_period.0, self.delay_period.1)
+    /// Returns the aggregated signature bytes
+    pub fn signature(&self) -> &[u8] {
+        &self.sync_committee_signature
     }
+}
 
-    /// Returns the proofs
-    pub fn proofs(&self) -> Result<Proofs> {
-        let height = self.proof_height();
-        let consensus_proof = ConsensusProof::new(self.proof_consensus(), height).map_err(|e| Error::DecodingError(e.to_string()))?;
-        Proofs::new(
-            self.proof_connection(),
-            Some(self.proof_client()),
+/// An Ethereum Altair light-client update: an attested header signed by
+/// the current sync committee, the next sync committee (to rotate in
+/// once its period starts), and a finalized header checkpoint.
+#[derive(
+    Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct EthBeaconHeader {
+    attested_header: BeaconBlockHeader,
+    next_sync_committee: Vec<Vec<u8>>,
+    next_sync_committee_aggregate: Vec<u8>,
+    next_sync_committee_branch: Vec<Vec<u8>>,
+    finalized_header: BeaconBlockHeader,
+    finality_branch: Vec<Vec<u8>>,
+    sync_aggregate: SyncAggregate,
+    fork_version: Vec<u8>,
+}
+
+impl EthBeaconHeader {
+    /// Returns a new Ethereum beacon-chain light-client update
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        attested_header: BeaconBlockHeader,
+        next_sync_committee: Vec<Vec<u8>>,
+        next_sync_committee_aggregate: Vec<u8>,
+        next_sync_committee_branch: Vec<Vec<u8>>,
+        finalized_header: BeaconBlockHeader,
+        finality_branch: Vec<Vec<u8>>,
+        sync_aggregate: SyncAggregate,
+        fork_version: Vec<u8>,
+    ) -> Self {
+        Self {
+            attested_header,
+            next_sync_committee,
+            next_sync_committee_aggregate,
+            next_sync_committee_branch,
+            finalized_header,
+            finality_branch,
+            sync_aggregate,
+            fork_version,
+        }
+    }
+}
+
+/// Verifies an Ethereum Altair light-client `update` against the current
+/// `client_state`/`consensus_state` and returns the consensus state to
+/// store next: the sync committee rotates from `current` to `next` once
+/// the finalized header's sync-committee period advances past the
+/// stored one.
+pub fn verify_update(
+    client_state: &EthBeaconClientState,
+    consensus_state: &EthBeaconConsensusState,
+    update: &EthBeaconHeader,
+) -> Result<EthBeaconConsensusState> {
+    if client_state.is_frozen() {
+        return Err(Error::ProofVerificationFailure(
+            "the Ethereum beacon-chain client is frozen".to_owned(),
+        ));
+    }
+
+    let attested_root = update.attested_header.hash_tree_root()?;
+
+    let next_committee_root = hash_tree_root_sync_committee(
+        &update.next_sync_committee,
+        &update.next_sync_committee_aggregate,
+    )?;
+    verify_merkle_branch(
+        next_committee_root,
+        &update.next_sync_committee_branch,
+        NEXT_SYNC_COMMITTEE_GENERALIZED_INDEX,
+        as_chunk(update.attested_header.state_root())?,
+    )?;
+
+    let participants = update
+        .sync_aggregate
+        .participants(consensus_state.current_sync_committee())?;
+    if participants.len() * 3 <= SYNC_COMMITTEE_SIZE * 2 {
+        return Err(Error::ProofVerificationFailure(
+            "the sync aggregate doesn't have the required 2/3 \
+             participation"
+                .to_owned(),
+        ));
+    }
+
+    let domain = compute_sync_committee_domain(
+        &update.fork_version,
+        client_state.genesis_validators_root(),
+    )?;
+    let signing_root = sha256_pair(&attested_root, &domain);
+    verify_bls_aggregate(
+        &participants,
+        &signing_root,
+        update.sync_aggregate.signature(),
+    )?;
+
+    let finalized_root = update.finalized_header.hash_tree_root()?;
+    verify_merkle_branch(
+        finalized_root,
+        &update.finality_branch,
+        FINALIZED_ROOT_GENERALIZED_INDEX,
+        as_chunk(update.attested_header.state_root())?,
+    )?;
+
+    let current_period =
+        sync_committee_period(consensus_state.finalized_header().slot());
+    let new_period = sync_committee_period(update.finalized_header.slot());
+    let (current_sync_committee, current_sync_committee_aggregate) =
+        if new_period > current_period {
+            (
+                update.next_sync_committee.clone(),
+                update.next_sync_committee_aggregate.clone(),
+            )
+        } else {
+            (
+                consensus_state.current_sync_committee.clone(),
+                consensus_state.current_sync_committee_aggregate.clone(),
+            )
+        };
+
+    Ok(EthBeaconConsensusState {
+        finalized_header: update.finalized_header.clone(),
+        current_sync_committee,
+        current_sync_committee_aggregate,
+    })
+}
+
+/// The number of slots in an Altair sync committee period (`SLOTS_PER_EPOCH
+/// * EPOCHS_PER_SYNC_COMMITTEE_PERIOD` = `32 * 256`).
+const SLOTS_PER_SYNC_COMMITTEE_PERIOD: u64 = 32 * 256;
+
+fn sync_committee_period(slot: u64) -> u64 {
+    slot / SLOTS_PER_SYNC_COMMITTEE_PERIOD
+}
+
+fn as_chunk(bytes: &[u8]) -> Result<[u8; 32]> {
+    bytes.try_into().map_err(|_| {
+        Error::ProofVerificationFailure(
+            "expected a 32-byte SSZ root".to_owned(),
+        )
+    })
+}
+
+fn sha256_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    let digest = hasher.finalize();
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&digest);
+    out
+}
+
+/// Merkleizes `chunks` (padding with zero chunks up to the next power of
+/// two no smaller than `limit`) into a single SSZ root.
+fn ssz_merkleize(chunks: &[[u8; 32]], limit: usize) -> [u8; 32] {
+    let mut size = 1usize;
+    while size < limit.max(1) {
+        size <<= 1;
+    }
+    let mut layer = chunks.to_vec();
+    layer.resize(size, [0u8; 32]);
+    while layer.len() > 1 {
+        let mut next = Vec::with_capacity(layer.len() / 2);
+        for pair in layer.chunks_exact(2) {
+            next.push(sha256_pair(&pair[0], &pair[1]));
+        }
+        layer = next;
+    }
+    layer[0]
+}
+
+/// Splits a 48-byte compressed BLS public key into its two 32-byte SSZ
+/// chunks (the second one zero-padded).
+fn pack_pubkey(pubkey: &[u8]) -> Result<[[u8; 32]; 2]> {
+    if pubkey.len() != BLS_PUBLIC_KEY_BYTES {
+        return Err(Error::ProofVerificationFailure(
+            "a BLS public key must be 48 bytes".to_owned(),
+        ));
+    }
+    let mut first = [0u8; 32];
+    let mut second = [0u8; 32];
+    first.copy_from_slice(&pubkey[..32]);
+    second[..16].copy_from_slice(&pubkey[32..48]);
+    Ok([first, second])
+}
+
+/// Computes the SSZ `hash_tree_root` of a `SyncCommittee` container
+/// (`pubkeys: Vector[BLSPubkey, 512]`, `aggregate_pubkey: BLSPubkey`).
+fn hash_tree_root_sync_committee(
+    pubkeys: &[Vec<u8>],
+    aggregate_pubkey: &[u8],
+) -> Result<[u8; 32]> {
+    if pubkeys.len() != SYNC_COMMITTEE_SIZE {
+        return Err(Error::ProofVerificationFailure(format!(
+            "a sync committee must have exactly {} public keys",
+            SYNC_COMMITTEE_SIZE
+        )));
+    }
+    let mut chunks = Vec::with_capacity(pubkeys.len() * 2);
+    for pk in pubkeys {
+        chunks.extend_from_slice(&pack_pubkey(pk)?);
+    }
+    let pubkeys_root = ssz_merkleize(&chunks, chunks.len());
+    let aggregate_root =
+        ssz_merkleize(&pack_pubkey(aggregate_pubkey)?, 2);
+    Ok(sha256_pair(&pubkeys_root, &aggregate_root))
+}
+
+/// Verifies `branch` proves `leaf` sits at `generalized_index` under
+/// `root`, folding sibling hashes up from the leaf and picking the
+/// left/right order at each level from the index's bits.
+fn verify_merkle_branch(
+    leaf: [u8; 32],
+    branch: &[Vec<u8>],
+    generalized_index: u64,
+    root: [u8; 32],
+) -> Result<()> {
+    let mut computed = leaf;
+    let mut index = generalized_index;
+    for sibling in branch {
+        let sibling = as_chunk(sibling)?;
+        computed = if index & 1 == 1 {
+            sha256_pair(&sibling, &computed)
+        } else {
+            sha256_pair(&computed, &sibling)
+        };
+        index >>= 1;
+    }
+    if computed != root {
+        return Err(Error::ProofVerificationFailure(
+            "the Merkle branch doesn't lead to the expected root"
+                .to_owned(),
+        ));
+    }
+    Ok(())
+}
+
+/// Computes the SSZ `hash_tree_root` of a `ForkData(current_version,
+/// genesis_validators_root)` container, per the Altair `compute_domain`
+/// algorithm: the 4-byte fork version is zero-padded to a chunk and
+/// paired with the genesis validators root.
+fn compute_fork_data_root(
+    fork_version: &[u8],
+    genesis_validators_root: &[u8; 32],
+) -> Result<[u8; 32]> {
+    if fork_version.len() != 4 {
+        return Err(Error::ProofVerificationFailure(
+            "a fork version must be 4 bytes".to_owned(),
+        ));
+    }
+    let mut version_chunk = [0u8; 32];
+    version_chunk[..4].copy_from_slice(fork_version);
+    Ok(sha256_pair(&version_chunk, genesis_validators_root))
+}
+
+/// `DOMAIN_SYNC_COMMITTEE` mixed with the active fork version and the
+/// client's genesis validators root, as the domain half of an Altair
+/// signing root: `domain_type || hash_tree_root(ForkData(fork_version,
+/// genesis_validators_root))[:28]`. Mixing in the genesis validators
+/// root is what lets the domain distinguish one network/fork from
+/// another sharing the same fork version bytes.
+fn compute_sync_committee_domain(
+    fork_version: &[u8],
+    genesis_validators_root: &[u8],
+) -> Result<[u8; 32]> {
+    let genesis_validators_root = as_chunk(genesis_validators_root)?;
+    let fork_data_root =
+        compute_fork_data_root(fork_version, &genesis_validators_root)?;
+    let mut out = [0u8; 32];
+    out[..4].copy_from_slice(&SYNC_COMMITTEE_DOMAIN_TYPE);
+    out[4..].copy_from_slice(&fork_data_root[..28]);
+    Ok(out)
+}
+
+/// The domain separation tag for BLS signatures over SSZ signing roots,
+/// per the Ethereum consensus-layer BLS spec.
+const BLS_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSZ_RO_POP_";
+
+/// Aggregates `pubkeys` and verifies `signature` over `message`.
+fn verify_bls_aggregate(
+    pubkeys: &[Vec<u8>],
+    message: &[u8; 32],
+    signature: &[u8],
+) -> Result<()> {
+    let parsed = pubkeys
+        .iter()
+        .map(|pk| {
+            blst::min_pk::PublicKey::from_bytes(pk).map_err(|_| {
+                Error::ProofVerificationFailure(
+                    "a sync committee public key is malformed".to_owned(),
+                )
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let refs: Vec<&blst::min_pk::PublicKey> = parsed.iter().collect();
+    let aggregate = blst::min_pk::AggregatePublicKey::aggregate(&refs, true)
+        .map_err(|_| {
+            Error::ProofVerificationFailure(
+                "failed to aggregate the sync committee public keys"
+                    .to_owned(),
+            )
+        })?;
+    let signature =
+        blst::min_pk::Signature::from_bytes(signature).map_err(|_| {
+            Error::ProofVerificationFailure(
+                "the sync committee signature is malformed".to_owned(),
+            )
+        })?;
+    let result = signature.verify(
+        true,
+        message,
+        BLS_DST,
+        &[],
+        &aggregate.to_public_key(),
+        true,
+    );
+    if result != blst::BLST_ERROR::BLST_SUCCESS {
+        return Err(Error::ProofVerificationFailure(
+            "the sync committee signature is invalid".to_owned(),
+        ));
+    }
+    Ok(())
+}
+
+/// A decoded client state: either the Tendermint (or other `ibc`-crate
+/// native) state, a first-class ICS06 solo-machine state, or an Ethereum
+/// Altair beacon-chain state.
+#[derive(Debug, Clone)]
+pub enum AnyClientStateData {
+    /// A Tendermint (or mock) client state
+    Tendermint(AnyClientState),
+    /// An ICS06 solo-machine client state
+    SoloMachine(SoloMachineClientState),
+    /// An Ethereum Altair beacon-chain client state
+    EthBeacon(EthBeaconClientState),
+}
+
+/// A decoded consensus state: either the Tendermint (or other
+/// `ibc`-crate native) state, a first-class ICS06 solo-machine state, or
+/// an Ethereum Altair beacon-chain state.
+#[derive(Debug, Clone)]
+pub enum AnyConsensusStateData {
+    /// A Tendermint (or mock) consensus state
+    Tendermint(AnyConsensusState),
+    /// An ICS06 solo-machine consensus state
+    SoloMachine(SoloMachineConsensusState),
+    /// An Ethereum Altair beacon-chain consensus state
+    EthBeacon(EthBeaconConsensusState),
+}
+
+/// A decoded client header: either the Tendermint (or other `ibc`-crate
+/// native) header, a first-class ICS06 solo-machine header, or an
+/// Ethereum Altair light-client update.
+#[derive(Debug, Clone)]
+pub enum AnyHeaderData {
+    /// A Tendermint (or mock) header
+    Tendermint(AnyHeader),
+    /// An ICS06 solo-machine header
+    SoloMachine(SoloMachineHeader),
+    /// An Ethereum Altair light-client update
+    EthBeacon(EthBeaconHeader),
+}
+
+/// Wraps a client state as a CosmWasm-hosted client state identified by
+/// `checksum`, unless it is already wrapped.
+fn wrap_wasm_client_state(
+    checksum: &[u8],
+    client_state: &AnyClientState,
+) -> AnyClientState {
+    match client_state {
+        AnyClientState::Wasm(_) => client_state.clone(),
+        _ => AnyClientState::wasm(
+            checksum.to_vec(),
+            client_state
+                .encode_vec()
+                .expect("Encoding a client state shouldn't fail"),
+        ),
+    }
+}
+
+/// Wraps a consensus state as a CosmWasm-hosted consensus state
+/// identified by `checksum`, unless it is already wrapped.
+fn wrap_wasm_consensus_state(
+    checksum: &[u8],
+    consensus_state: &AnyConsensusState,
+) -> AnyConsensusState {
+    match consensus_state {
+        AnyConsensusState::Wasm(_) => consensus_state.clone(),
+        _ => AnyConsensusState::wasm(
+            checksum.to_vec(),
+            consensus_state
+                .encode_vec()
+                .expect("Encoding a consensus state shouldn't fail"),
+        ),
+    }
+}
+
+/// Wraps a header as a CosmWasm-hosted header identified by `checksum`,
+/// unless it is already wrapped.
+fn wrap_wasm_header(checksum: &[u8], header: &AnyHeader) -> AnyHeader {
+    match header {
+        AnyHeader::Wasm(_) => header.clone(),
+        _ => AnyHeader::wasm(
+            checksum.to_vec(),
+            header
+                .encode_vec()
+                .expect("Encoding a header shouldn't fail"),
+        ),
+    }
+}
+
+/// Unwraps a CosmWasm-hosted client state into the concrete client state
+/// it carries, passing non-wasm states through unchanged.
+fn unwrap_wasm_client_state(
+    client_state: AnyClientState,
+) -> Result<AnyClientState> {
+    match client_state {
+        AnyClientState::Wasm(wasm) => AnyClientState::decode_vec(&wasm.data)
+            .map_err(|e| Error::DecodingError(e.to_string())),
+        other => Ok(other),
+    }
+}
+
+/// Unwraps a CosmWasm-hosted consensus state into the concrete consensus
+/// state it carries, passing non-wasm states through unchanged.
+fn unwrap_wasm_consensus_state(
+    consensus_state: AnyConsensusState,
+) -> Result<AnyConsensusState> {
+    match consensus_state {
+        AnyConsensusState::Wasm(wasm) => {
+            AnyConsensusState::decode_vec(&wasm.data)
+                .map_err(|e| Error::DecodingError(e.to_string()))
+        }
+        other => Ok(other),
+    }
+}
+
+/// Unwraps a CosmWasm-hosted header into the concrete header it carries,
+/// passing non-wasm headers through unchanged.
+fn unwrap_wasm_header(header: AnyHeader) -> Result<AnyHeader> {
+    match header {
+        AnyHeader::Wasm(wasm) => AnyHeader::decode_vec(&wasm.data)
+            .map_err(|e| Error::DecodingError(e.to_string())),
+        other => Ok(other),
+    }
+}
+
+#[derive(
+    Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+enum StoredClientState {
+    Tendermint(Vec<u8>),
+    SoloMachine(SoloMachineClientState),
+    EthBeacon(EthBeaconClientState),
+}
+
+#[derive(
+    Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+enum StoredConsensusState {
+    Tendermint(Vec<u8>),
+    SoloMachine(SoloMachineConsensusState),
+    EthBeacon(EthBeaconConsensusState),
+}
+
+#[derive(
+    Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+enum StoredHeader {
+    Tendermint(Vec<u8>),
+    SoloMachine(SoloMachineHeader),
+    EthBeacon(EthBeaconHeader),
+}
+
+/// States to create a new client
+#[derive(
+    Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct ClientCreationData {
+    /// The client state
+    client_state: StoredClientState,
+    /// The consensus state
+    consensus_state: StoredConsensusState,
+    /// The code checksum of the wasm blob implementing the client, if
+    /// the client and consensus states are CosmWasm-hosted
+    checksum: Option<Vec<u8>>,
+}
+
+impl ClientCreationData {
+    /// Returns the data to create a new Tendermint (or other `ibc`-crate
+    /// native) client.
+    pub fn new(
+        client_state: AnyClientState,
+        consensus_state: AnyConsensusState,
+    ) -> Self {
+        let client_state = client_state
+            .encode_vec()
+            .expect("Encoding a client state shouldn't fail");
+        let consensus_state = consensus_state
+            .encode_vec()
+            .expect("Encoding a consensus state shouldn't fail");
+        Self {
+            client_state: StoredClientState::Tendermint(client_state),
+            consensus_state: StoredConsensusState::Tendermint(
+                consensus_state,
+            ),
+            checksum: None,
+        }
+    }
+
+    /// Returns the data to create a new CosmWasm-hosted client. The
+    /// client and consensus states are wrapped as CosmWasm-hosted states
+    /// identified by `checksum` before being stored.
+    pub fn new_wasm(
+        client_state: AnyClientState,
+        consensus_state: AnyConsensusState,
+        checksum: Vec<u8>,
+    ) -> Self {
+        let client_state = wrap_wasm_client_state(&checksum, &client_state);
+        let consensus_state =
+            wrap_wasm_consensus_state(&checksum, &consensus_state);
+        let client_state = client_state
+            .encode_vec()
+            .expect("Encoding a client state shouldn't fail");
+        let consensus_state = consensus_state
+            .encode_vec()
+            .expect("Encoding a consensus state shouldn't fail");
+        Self {
+            client_state: StoredClientState::Tendermint(client_state),
+            consensus_state: StoredConsensusState::Tendermint(
+                consensus_state,
+            ),
+            checksum: Some(checksum),
+        }
+    }
+
+    /// Returns the data to create a new ICS06 solo-machine client
+    pub fn new_solo_machine(
+        client_state: SoloMachineClientState,
+        consensus_state: SoloMachineConsensusState,
+    ) -> Self {
+        Self {
+            client_state: StoredClientState::SoloMachine(client_state),
+            consensus_state: StoredConsensusState::SoloMachine(
+                consensus_state,
+            ),
+            checksum: None,
+        }
+    }
+
+    /// Returns the data to create a new Ethereum Altair beacon-chain
+    /// client
+    pub fn new_eth_beacon(
+        client_state: EthBeaconClientState,
+        consensus_state: EthBeaconConsensusState,
+    ) -> Self {
+        Self {
+            client_state: StoredClientState::EthBeacon(client_state),
+            consensus_state: StoredConsensusState::EthBeacon(
+                consensus_state,
+            ),
+            checksum: None,
+        }
+    }
+
+    /// Returns the code checksum, if this client is CosmWasm-hosted
+    pub fn checksum(&self) -> Option<&[u8]> {
+        self.checksum.as_deref()
+    }
+
+    /// Returns the client state
+    pub fn client_state(&self) -> Result<AnyClientStateData> {
+        match &self.client_state {
+            StoredClientState::Tendermint(bytes) => {
+                let state = AnyClientState::decode_vec(bytes)
+                    .map_err(|e| Error::DecodingError(e.to_string()))?;
+                let state = if self.checksum.is_some() {
+                    unwrap_wasm_client_state(state)?
+                } else {
+                    state
+                };
+                Ok(AnyClientStateData::Tendermint(state))
+            }
+            StoredClientState::SoloMachine(state) => {
+                Ok(AnyClientStateData::SoloMachine(state.clone()))
+            }
+            StoredClientState::EthBeacon(state) => {
+                Ok(AnyClientStateData::EthBeacon(state.clone()))
+            }
+        }
+    }
+
+    /// Returns the consensus state
+    pub fn consensus_state(&self) -> Result<AnyConsensusStateData> {
+        match &self.consensus_state {
+            StoredConsensusState::Tendermint(bytes) => {
+                let state = AnyConsensusState::decode_vec(bytes)
+                    .map_err(|e| Error::DecodingError(e.to_string()))?;
+                let state = if self.checksum.is_some() {
+                    unwrap_wasm_consensus_state(state)?
+                } else {
+                    state
+                };
+                Ok(AnyConsensusStateData::Tendermint(state))
+            }
+            StoredConsensusState::SoloMachine(state) => {
+                Ok(AnyConsensusStateData::SoloMachine(state.clone()))
+            }
+            StoredConsensusState::EthBeacon(state) => {
+                Ok(AnyConsensusStateData::EthBeacon(state.clone()))
+            }
+        }
+    }
+}
+
+/// Data to update a client
+#[derive(
+    Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct ClientUpdateData {
+    /// The updated client ID
+    client_id: String,
+    /// The headers to update the client
+    headers: Vec<StoredHeader>,
+    /// The code checksum of the wasm blob implementing the client, if
+    /// the headers are for a CosmWasm-hosted client
+    checksum: Option<Vec<u8>>,
+}
+
+impl ClientUpdateData {
+    /// Returns the data to update a client with Tendermint (or other
+    /// `ibc`-crate native) headers.
+    pub fn new(client_id: ClientId, headers: Vec<AnyHeader>) -> Self {
+        let client_id = client_id.as_str().to_owned();
+        let headers = headers
+            .iter()
+            .map(|h| {
+                let bytes = h
+                    .encode_vec()
+                    .expect("Encoding a client header shouldn't fail");
+                StoredHeader::Tendermint(bytes)
+            })
+            .collect();
+        Self {
+            client_id,
+            headers,
+            checksum: None,
+        }
+    }
+
+    /// Returns the data to update a CosmWasm-hosted client. The headers
+    /// are wrapped as CosmWasm-hosted headers identified by `checksum`
+    /// before being stored.
+    pub fn new_wasm(
+        client_id: ClientId,
+        headers: Vec<AnyHeader>,
+        checksum: Vec<u8>,
+    ) -> Self {
+        let client_id = client_id.as_str().to_owned();
+        let headers = headers
+            .iter()
+            .map(|h| {
+                let h = wrap_wasm_header(&checksum, h);
+                let bytes = h
+                    .encode_vec()
+                    .expect("Encoding a client header shouldn't fail");
+                StoredHeader::Tendermint(bytes)
+            })
+            .collect();
+        Self {
+            client_id,
+            headers,
+            checksum: Some(checksum),
+        }
+    }
+
+    /// Returns the data to update a client with ICS06 solo-machine headers
+    pub fn new_solo_machine(
+        client_id: ClientId,
+        headers: Vec<SoloMachineHeader>,
+    ) -> Self {
+        let client_id = client_id.as_str().to_owned();
+        let headers =
+            headers.into_iter().map(StoredHeader::SoloMachine).collect();
+        Self {
+            client_id,
+            headers,
+            checksum: None,
+        }
+    }
+
+    /// Returns the client ID
+    pub fn client_id(&self) -> Result<ClientId> {
+        ClientId::from_str(&self.client_id)
+            .map_err(|e| Error::DecodingError(e.to_string()))
+    }
+
+    /// Returns the data to update a client with Ethereum Altair
+    /// light-client updates
+    pub fn new_eth_beacon(
+        client_id: ClientId,
+        headers: Vec<EthBeaconHeader>,
+    ) -> Self {
+        let client_id = client_id.as_str().to_owned();
+        let headers =
+            headers.into_iter().map(StoredHeader::EthBeacon).collect();
+        Self {
+            client_id,
+            headers,
+            checksum: None,
+        }
+    }
+
+    /// Returns the code checksum, if these headers are CosmWasm-hosted
+    pub fn checksum(&self) -> Option<&[u8]> {
+        self.checksum.as_deref()
+    }
+
+    /// Returns the headers
+    pub fn headers(&self) -> Result<Vec<AnyHeaderData>> {
+        let mut headers = vec![];
+        for h in &self.headers {
+            let header = match h {
+                StoredHeader::Tendermint(bytes) => {
+                    let header = AnyHeader::decode_vec(bytes).map_err(
+                        |e| Error::DecodingError(e.to_string()),
+                    )?;
+                    let header = if self.checksum.is_some() {
+                        unwrap_wasm_header(header)?
+                    } else {
+                        header
+                    };
+                    AnyHeaderData::Tendermint(header)
+                }
+                StoredHeader::SoloMachine(header) => {
+                    AnyHeaderData::SoloMachine(header.clone())
+                }
+                StoredHeader::EthBeacon(header) => {
+                    AnyHeaderData::EthBeacon(header.clone())
+                }
+            };
+            headers.push(header);
+        }
+        Ok(headers)
+    }
+}
+
+/// Data to upgrade a client
+#[derive(
+    Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct ClientUpgradeData {
+    /// The upgraded client ID
+    client_id: String,
+    /// The client state
+    client_state: Vec<u8>,
+    /// The consensus state
+    consensus_state: Vec<u8>,
+    /// The proof of the client state
+    proof_client: Vec<u8>,
+    /// The proof of the consensus state
+    proof_consensus_state: Vec<u8>,
+    /// The code checksum of the wasm blob implementing the client, if
+    /// the upgraded client and consensus states are CosmWasm-hosted
+    checksum: Option<Vec<u8>>,
+}
+
+impl ClientUpgradeData {
+    /// Returns the data to upgrade a client.
+    pub fn new(
+        client_id: ClientId,
+        client_state: AnyClientState,
+        consensus_state: AnyConsensusState,
+        client_proof: MerkleProof,
+        consensus_proof: MerkleProof,
+    ) -> Self {
+        Self::new_inner(
+            client_id,
+            client_state,
+            consensus_state,
+            client_proof,
+            consensus_proof,
+            None,
+        )
+    }
+
+    /// Returns the data to upgrade a CosmWasm-hosted client. The
+    /// upgraded client and consensus states are wrapped as
+    /// CosmWasm-hosted states identified by `checksum` before being
+    /// stored.
+    pub fn new_wasm(
+        client_id: ClientId,
+        client_state: AnyClientState,
+        consensus_state: AnyConsensusState,
+        client_proof: MerkleProof,
+        consensus_proof: MerkleProof,
+        checksum: Vec<u8>,
+    ) -> Self {
+        Self::new_inner(
+            client_id,
+            client_state,
+            consensus_state,
+            client_proof,
+            consensus_proof,
+            Some(checksum),
+        )
+    }
+
+    fn new_inner(
+        client_id: ClientId,
+        client_state: AnyClientState,
+        consensus_state: AnyConsensusState,
+        client_proof: MerkleProof,
+        consensus_proof: MerkleProof,
+        checksum: Option<Vec<u8>>,
+    ) -> Self {
+        let client_id = client_id.as_str().to_owned();
+        let client_state = match &checksum {
+            Some(checksum) => wrap_wasm_client_state(checksum, &client_state),
+            None => client_state,
+        };
+        let consensus_state = match &checksum {
+            Some(checksum) => {
+                wrap_wasm_consensus_state(checksum, &consensus_state)
+            }
+            None => consensus_state,
+        };
+        let client_state = client_state
+            .encode_vec()
+            .expect("Encoding a client state shouldn't fail");
+        let consensus_state = consensus_state
+            .encode_vec()
+            .expect("Encoding a consensus state shouldn't fail");
+        let mut proof_client = vec![];
+        client_proof
+            .encode(&mut proof_client)
+            .expect("Encoding a client proof shouldn't fail");
+        let mut proof_consensus_state = vec![];
+        consensus_proof
+            .encode(&mut proof_consensus_state)
+            .expect("Encoding a consensus proof shouldn't fail");
+        Self {
+            client_id,
+            client_state,
+            consensus_state,
+            proof_client,
+            proof_consensus_state,
+            checksum,
+        }
+    }
+
+    /// Returns the client ID
+    pub fn client_id(&self) -> Result<ClientId> {
+        ClientId::from_str(&self.client_id)
+            .map_err(|e| Error::DecodingError(e.to_string()))
+    }
+
+    /// Returns the code checksum, if this client is CosmWasm-hosted
+    pub fn checksum(&self) -> Option<&[u8]> {
+        self.checksum.as_deref()
+    }
+
+    /// Returns the client state
+    pub fn client_state(&self) -> Result<AnyClientState> {
+        let state = AnyClientState::decode_vec(&self.client_state)
+            .map_err(|e| Error::DecodingError(e.to_string()))?;
+        if self.checksum.is_some() {
+            unwrap_wasm_client_state(state)
+        } else {
+            Ok(state)
+        }
+    }
+
+    /// Returns the consensus state
+    pub fn consensus_state(&self) -> Result<AnyConsensusState> {
+        let state = AnyConsensusState::decode_vec(&self.consensus_state)
+            .map_err(|e| Error::DecodingError(e.to_string()))?;
+        if self.checksum.is_some() {
+            unwrap_wasm_consensus_state(state)
+        } else {
+            Ok(state)
+        }
+    }
+
+    /// Returns the proof for client state
+    pub fn proof_client(&self) -> Result<MerkleProof> {
+        MerkleProof::decode(&self.proof_client[..])
+            .map_err(|e| Error::DecodingError(e.to_string()))
+    }
+
+    /// Returns the proof for consensus state
+    pub fn proof_consensus_state(&self) -> Result<MerkleProof> {
+        MerkleProof::decode(&self.proof_consensus_state[..])
+            .map_err(|e| Error::DecodingError(e.to_string()))
+    }
+}
+
+/// An encoded [`AnyClientState`], validated to decode successfully so a
+/// malformed relayer message is rejected at construction rather than
+/// decoding to an empty value later on. [`BorshDeserialize`] and
+/// [`Deserialize`] are hand-rolled to route through [`Self::try_new`]
+/// instead of deriving, since `ConnectionOpenTryData` (which embeds this
+/// type) is itself decoded wholesale from an incoming transaction: a
+/// derived impl would stuff unvalidated bytes straight into the tuple
+/// field and only catch the corruption lazily, the first time
+/// [`Self::decode`] is called.
+#[derive(Debug, Clone, BorshSerialize, Serialize)]
+pub struct EncodedClientState(Vec<u8>);
+
+impl EncodedClientState {
+    /// Encodes a client state. Encoding a valid client state cannot fail.
+    fn new(client_state: &AnyClientState) -> Self {
+        Self(
+            client_state
+                .encode_vec()
+                .expect("Encoding a client state shouldn't fail"),
+        )
+    }
+
+    /// Validates and wraps already-encoded client state bytes
+    pub fn try_new(bytes: Vec<u8>) -> Result<Self> {
+        AnyClientState::decode_vec(&bytes)
+            .map_err(|e| Error::InvalidClientState(e.to_string()))?;
+        Ok(Self(bytes))
+    }
+
+    /// Decodes the wrapped client state
+    pub fn decode(&self) -> Result<AnyClientState> {
+        AnyClientState::decode_vec(&self.0)
+            .map_err(|e| Error::InvalidClientState(e.to_string()))
+    }
+}
+
+impl BorshDeserialize for EncodedClientState {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        let bytes = Vec::<u8>::deserialize(buf)?;
+        Self::try_new(bytes)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+}
+
+impl<'de> Deserialize<'de> for EncodedClientState {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let bytes = Vec::<u8>::deserialize(deserializer)?;
+        Self::try_new(bytes).map_err(serde::de::Error::custom)
+    }
+}
+
+/// An encoded ICS03 [`Counterparty`], validated to decode successfully.
+/// See [`EncodedClientState`] for why [`BorshDeserialize`] and
+/// [`Deserialize`] are hand-rolled rather than derived.
+#[derive(Debug, Clone, BorshSerialize, Serialize)]
+pub struct EncodedCounterparty(Vec<u8>);
+
+impl EncodedCounterparty {
+    /// Encodes a counterparty. Encoding a valid counterparty cannot fail.
+    fn new(counterparty: Counterparty) -> Self {
+        // TODO: Need Profobuf implementation for Counterparty in ibc-rs
+        // let counterparty = counterparty.encode_vec().expect("Encoding a
+        // counterparty shouldn't fail");
+        let mut bytes = vec![];
+        RawCounterparty::from(counterparty)
+            .encode(&mut bytes)
+            .expect("Encoding a counterparty shouldn't fail");
+        Self(bytes)
+    }
+
+    /// Validates and wraps already-encoded counterparty bytes
+    pub fn try_new(bytes: Vec<u8>) -> Result<Self> {
+        let raw = RawCounterparty::decode(&bytes[..])
+            .map_err(|e| Error::InvalidCounterparty(e.to_string()))?;
+        let _: Counterparty = raw
+            .try_into()
+            .map_err(|e| Error::InvalidCounterparty(format!("{:?}", e)))?;
+        Ok(Self(bytes))
+    }
+
+    /// Decodes the wrapped counterparty
+    pub fn decode(&self) -> Result<Counterparty> {
+        let raw = RawCounterparty::decode(&self.0[..])
+            .map_err(|e| Error::InvalidCounterparty(e.to_string()))?;
+        raw.try_into()
+            .map_err(|e| Error::InvalidCounterparty(format!("{:?}", e)))
+    }
+}
+
+impl BorshDeserialize for EncodedCounterparty {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        let bytes = Vec::<u8>::deserialize(buf)?;
+        Self::try_new(bytes)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+}
+
+impl<'de> Deserialize<'de> for EncodedCounterparty {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let bytes = Vec::<u8>::deserialize(deserializer)?;
+        Self::try_new(bytes).map_err(serde::de::Error::custom)
+    }
+}
+
+/// An encoded ICS03 [`Version`], validated to decode successfully. See
+/// [`EncodedClientState`] for why [`BorshDeserialize`] and [`Deserialize`]
+/// are hand-rolled rather than derived.
+#[derive(Debug, Clone, BorshSerialize, Serialize)]
+pub struct EncodedVersion(Vec<u8>);
+
+impl EncodedVersion {
+    /// Encodes a version. Encoding a valid version cannot fail.
+    fn new(version: &Version) -> Self {
+        Self(version.encode_vec().expect("Encoding a version shouldn't fail"))
+    }
+
+    /// Validates and wraps already-encoded version bytes
+    pub fn try_new(bytes: Vec<u8>) -> Result<Self> {
+        Version::decode_vec(&bytes)
+            .map_err(|e| Error::InvalidVersion(e.to_string()))?;
+        Ok(Self(bytes))
+    }
+
+    /// Decodes the wrapped version
+    pub fn decode(&self) -> Result<Version> {
+        Version::decode_vec(&self.0)
+            .map_err(|e| Error::InvalidVersion(e.to_string()))
+    }
+}
+
+impl BorshDeserialize for EncodedVersion {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        let bytes = Vec::<u8>::deserialize(buf)?;
+        Self::try_new(bytes)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+}
+
+impl<'de> Deserialize<'de> for EncodedVersion {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let bytes = Vec::<u8>::deserialize(deserializer)?;
+        Self::try_new(bytes).map_err(serde::de::Error::custom)
+    }
+}
+
+/// An encoded ICS04 [`ChannelEnd`], validated to decode successfully. See
+/// [`EncodedClientState`] for why [`BorshDeserialize`] and [`Deserialize`]
+/// are hand-rolled rather than derived.
+#[derive(Debug, Clone, BorshSerialize, Serialize)]
+pub struct EncodedChannelEnd(Vec<u8>);
+
+impl EncodedChannelEnd {
+    /// Encodes a channel end. Encoding a valid channel end cannot fail.
+    fn new(channel: &ChannelEnd) -> Self {
+        Self(
+            channel
+                .encode_vec()
+                .expect("Encoding a channel end shouldn't fail"),
+        )
+    }
+
+    /// Validates and wraps already-encoded channel end bytes
+    pub fn try_new(bytes: Vec<u8>) -> Result<Self> {
+        ChannelEnd::decode_vec(&bytes)
+            .map_err(|e| Error::InvalidChannel(e.to_string()))?;
+        Ok(Self(bytes))
+    }
+
+    /// Decodes the wrapped channel end
+    pub fn decode(&self) -> Result<ChannelEnd> {
+        ChannelEnd::decode_vec(&self.0)
+            .map_err(|e| Error::InvalidChannel(e.to_string()))
+    }
+}
+
+impl BorshDeserialize for EncodedChannelEnd {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        let bytes = Vec::<u8>::deserialize(buf)?;
+        Self::try_new(bytes)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+}
+
+impl<'de> Deserialize<'de> for EncodedChannelEnd {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let bytes = Vec::<u8>::deserialize(deserializer)?;
+        Self::try_new(bytes).map_err(serde::de::Error::custom)
+    }
+}
+
+/// An encoded ICS04 [`Packet`], validated to decode successfully. See
+/// [`EncodedClientState`] for why [`BorshDeserialize`] and [`Deserialize`]
+/// are hand-rolled rather than derived.
+#[derive(Debug, Clone, BorshSerialize, Serialize)]
+pub struct EncodedPacket(Vec<u8>);
+
+impl EncodedPacket {
+    /// Encodes a packet. Encoding a valid packet cannot fail.
+    fn new(packet: &Packet) -> Self {
+        Self(packet.encode_vec().expect("Encoding a packet shouldn't fail"))
+    }
+
+    /// Validates and wraps already-encoded packet bytes
+    pub fn try_new(bytes: Vec<u8>) -> Result<Self> {
+        Packet::decode_vec(&bytes)
+            .map_err(|e| Error::InvalidPacket(e.to_string()))?;
+        Ok(Self(bytes))
+    }
+
+    /// Decodes the wrapped packet
+    pub fn decode(&self) -> Result<Packet> {
+        Packet::decode_vec(&self.0)
+            .map_err(|e| Error::InvalidPacket(e.to_string()))
+    }
+}
+
+impl BorshDeserialize for EncodedPacket {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        let bytes = Vec::<u8>::deserialize(buf)?;
+        Self::try_new(bytes)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+}
+
+impl<'de> Deserialize<'de> for EncodedPacket {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let bytes = Vec::<u8>::deserialize(deserializer)?;
+        Self::try_new(bytes).map_err(serde::de::Error::custom)
+    }
+}
+
+/// An encoded commitment proof. Storage mirrors [`CommitmentProofBytes`]
+/// (infallible to and from raw bytes) but construction from an untrusted
+/// byte string validates that the bytes are a well-formed [`MerkleProof`],
+/// preserving the distinction between "empty" and "corrupt" input that a
+/// bare `Vec<u8>` loses. [`BorshDeserialize`] is hand-rolled, and the
+/// hex-preserving [`Deserialize`] below routes through [`Self::try_new`]
+/// too, for the same reason given on [`EncodedClientState`].
+#[derive(Debug, Clone, BorshSerialize)]
+pub struct EncodedProof(Vec<u8>);
+
+impl EncodedProof {
+    /// Wraps a commitment proof. The bytes are not checked here; only
+    /// [`Self::try_new`] and deserialization validate them.
+    fn new(proof: CommitmentProofBytes) -> Self {
+        Self(proof.into())
+    }
+
+    /// Validates and wraps already-encoded commitment proof bytes
+    pub fn try_new(bytes: Vec<u8>) -> Result<Self> {
+        MerkleProof::decode(&bytes[..])
+            .map_err(|e| Error::InvalidProof(e.to_string()))?;
+        Ok(Self(bytes))
+    }
+
+    /// Decodes the wrapped proof
+    pub fn decode(&self) -> Result<CommitmentProofBytes> {
+        Ok(self.0.clone().into())
+    }
+}
+
+impl BorshDeserialize for EncodedProof {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        let bytes = Vec::<u8>::deserialize(buf)?;
+        Self::try_new(bytes)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+}
+
+impl Serialize for EncodedProof {
+    /// Serializes via [`CommitmentProofBytes`], preserving its
+    /// hex-serialization semantics instead of serializing the raw bytes
+    /// like an ordinary `Vec<u8>`.
+    fn serialize<S>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let proof: CommitmentProofBytes = self.0.clone().into();
+        proof.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for EncodedProof {
+    /// Deserializes via [`CommitmentProofBytes`], preserving its
+    /// hex-serialization semantics, then validates the resulting bytes
+    /// decode to a well-formed [`MerkleProof`] via [`Self::try_new`]
+    /// instead of stuffing them into the tuple field directly.
+    fn deserialize<D>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let proof = CommitmentProofBytes::deserialize(deserializer)?;
+        Self::try_new(proof.into()).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Data to initialize a connection
+#[derive(
+    Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct ConnectionOpenInitData {
+    /// The corresponding client ID
+    client_id: String,
+    /// The corresponding counterparty
+    counterparty: EncodedCounterparty,
+    /// The version
+    version: EncodedVersion,
+    /// The delay period as (secs, nanos)
+    delay_period: (u64, u32),
+}
+
+impl ConnectionOpenInitData {
+    /// Returns the data to initalize a connection
+    pub fn new(
+        client_id: ClientId,
+        counterparty: Counterparty,
+        version: Version,
+        delay_period: Duration,
+    ) -> Self {
+        let client_id = client_id.as_str().to_owned();
+        Self {
+            client_id,
+            counterparty: EncodedCounterparty::new(counterparty),
+            version: EncodedVersion::new(&version),
+            delay_period: (delay_period.as_secs(), delay_period.subsec_nanos()),
+        }
+    }
+
+    /// Returns the client ID
+    pub fn client_id(&self) -> Option<ClientId> {
+        ClientId::from_str(&self.client_id).ok()
+    }
+
+    /// Returns the counterparty
+    pub fn counterparty(&self) -> Result<Counterparty> {
+        self.counterparty.decode()
+    }
+
+    /// Returns the version
+    pub fn version(&self) -> Result<Version> {
+        self.version.decode()
+    }
+
+    /// Returns the delay period
+    pub fn delay_period(&self) -> Duration {
+        Duration::new(self.delay_period.0, self.delay_period.1)
+    }
+}
+
+/// Data to try to open a connection
+#[derive(
+    Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct ConnectionOpenTryData {
+    prev_conn_id: Option<String>,
+    client_id: String,
+    client_state: EncodedClientState,
+    counterparty: EncodedCounterparty,
+    counterparty_versions: Vec<EncodedVersion>,
+    proof_height: (u64, u64),
+    proof_connection: EncodedProof,
+    proof_client: EncodedProof,
+    proof_consensus: EncodedProof,
+    delay_period: (u64, u32),
+}
+
+impl ConnectionOpenTryData {
+    /// Returns the data to try to open a connection
+    pub fn new(
+        prev_conn_id: Option<ConnectionId>,
+        client_id: ClientId,
+        client_state: AnyClientState,
+        counterparty: Counterparty,
+        counterparty_versions: Vec<Version>,
+        proof_height: Height,
+        proof_connection: CommitmentProofBytes,
+        proof_client: CommitmentProofBytes,
+        proof_consensus: CommitmentProofBytes,
+        delay_period: Duration,
+    ) -> Self {
+        let prev_conn_id = prev_conn_id.map(|id| id.as_str().to_owned());
+        let client_id = client_id.as_str().to_owned();
+        let client_state = EncodedClientState::new(&client_state);
+        let counterparty = EncodedCounterparty::new(counterparty);
+        let versions =
+            counterparty_versions.iter().map(EncodedVersion::new).collect();
+        Self {
+            prev_conn_id,
+            client_id,
+            client_state,
+            counterparty,
+            counterparty_versions: versions,
+            proof_height: (
+                proof_height.revision_number,
+                proof_height.revision_height,
+            ),
+            proof_connection: EncodedProof::new(proof_connection),
+            proof_client: EncodedProof::new(proof_client),
+            proof_consensus: EncodedProof::new(proof_consensus),
+            delay_period: (delay_period.as_secs(), delay_period.subsec_nanos()),
+        }
+    }
+
+    /// Returns the previous connection ID
+    pub fn previous_connection_id(&self) -> Option<ConnectionId> {
+        match &self.prev_conn_id {
+            Some(id) => ConnectionId::from_str(id).ok(),
+            None => None,
+        }
+    }
+
+    /// Returns the client ID
+    pub fn client_id(&self) -> Option<ClientId> {
+        ClientId::from_str(&self.client_id).ok()
+    }
+
+    /// Returns the client state
+    pub fn client_state(&self) -> Result<AnyClientState> {
+        self.client_state.decode()
+    }
+
+    /// Returns the counterparty
+    pub fn counterparty(&self) -> Result<Counterparty> {
+        self.counterparty.decode()
+    }
+
+    /// Returns the list of versions
+    pub fn counterparty_versions(&self) -> Result<Vec<Version>> {
+        self.counterparty_versions.iter().map(|v| v.decode()).collect()
+    }
+
+    /// Returns the height of the proofs
+    pub fn proof_height(&self) -> Height {
+        Height::new(self.proof_height.0, self.proof_height.1)
+    }
+
+    /// Returns the proof for connection
+    pub fn proof_connection(&self) -> Result<CommitmentProofBytes> {
+        self.proof_connection.decode()
+    }
+
+    /// Returns the proof for client state
+    pub fn proof_client(&self) -> Result<CommitmentProofBytes> {
+        self.proof_client.decode()
+    }
+
+    /// Returns the proof for consensus state
+    pub fn proof_consensus(&self) -> Result<CommitmentProofBytes> {
+        self.proof_consensus.decode()
+    }
+
+    /// Returns the delay period
+    pub fn delay_period(&self) -> Duration {
+        Duration::new(self.delay_period.0, self.delay_period.1)
+    }
+
+    /// Returns the proofs
+    pub fn proofs(&self) -> Result<Proofs> {
+        let height = self.proof_height();
+        let consensus_proof =
+            ConsensusProof::new(self.proof_consensus()?, height)
+                .map_err(|e| Error::DecodingError(e.to_string()))?;
+        Proofs::new(
+            self.proof_connection()?,
+            Some(self.proof_client()?),
             Some(consensus_proof),
             None,
             height,
-            ).map_err(|e| Error::DecodingError(e.to_string()))
+        )
+        .map_err(|e| Error::DecodingError(e.to_string()))
+    }
+}
+
+/// Data to acknowledge a connection
+#[derive(
+    Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct ConnectionOpenAckData {
+    conn_id: String,
+    counterparty_conn_id: String,
+    client_state: EncodedClientState,
+    version: EncodedVersion,
+    proof_height: (u64, u64),
+    proof_try: EncodedProof,
+    proof_client: EncodedProof,
+    proof_consensus: EncodedProof,
+}
+
+impl ConnectionOpenAckData {
+    /// Returns the data to acknowledge a connection
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        conn_id: ConnectionId,
+        counterparty_conn_id: ConnectionId,
+        client_state: AnyClientState,
+        version: Version,
+        proof_height: Height,
+        proof_try: CommitmentProofBytes,
+        proof_client: CommitmentProofBytes,
+        proof_consensus: CommitmentProofBytes,
+    ) -> Self {
+        let conn_id = conn_id.as_str().to_owned();
+        let counterparty_conn_id = counterparty_conn_id.as_str().to_owned();
+        let client_state = EncodedClientState::new(&client_state);
+        let version = EncodedVersion::new(&version);
+        Self {
+            conn_id,
+            counterparty_conn_id,
+            client_state,
+            version,
+            proof_height: (
+                proof_height.revision_number,
+                proof_height.revision_height,
+            ),
+            proof_try: EncodedProof::new(proof_try),
+            proof_client: EncodedProof::new(proof_client),
+            proof_consensus: EncodedProof::new(proof_consensus),
+        }
+    }
+
+    /// Returns the connection ID
+    pub fn connection_id(&self) -> Option<ConnectionId> {
+        ConnectionId::from_str(&self.conn_id).ok()
+    }
+
+    /// Returns the counterparty connection ID
+    pub fn counterparty_connection_id(&self) -> Option<ConnectionId> {
+        ConnectionId::from_str(&self.counterparty_conn_id).ok()
+    }
+
+    /// Returns the client state
+    pub fn client_state(&self) -> Result<AnyClientState> {
+        self.client_state.decode()
+    }
+
+    /// Returns the version
+    pub fn version(&self) -> Result<Version> {
+        self.version.decode()
+    }
+
+    /// Returns the height of the proofs
+    pub fn proof_height(&self) -> Height {
+        Height::new(self.proof_height.0, self.proof_height.1)
+    }
+
+    /// Returns the proof for the connection in the try state
+    pub fn proof_try(&self) -> Result<CommitmentProofBytes> {
+        self.proof_try.decode()
+    }
+
+    /// Returns the proof for client state
+    pub fn proof_client(&self) -> Result<CommitmentProofBytes> {
+        self.proof_client.decode()
+    }
+
+    /// Returns the proof for consensus state
+    pub fn proof_consensus(&self) -> Result<CommitmentProofBytes> {
+        self.proof_consensus.decode()
+    }
+
+    /// Returns the proofs
+    pub fn proofs(&self) -> Result<Proofs> {
+        let height = self.proof_height();
+        let consensus_proof =
+            ConsensusProof::new(self.proof_consensus()?, height)
+                .map_err(|e| Error::DecodingError(e.to_string()))?;
+        Proofs::new(
+            self.proof_try()?,
+            Some(self.proof_client()?),
+            Some(consensus_proof),
+            None,
+            height,
+        )
+        .map_err(|e| Error::DecodingError(e.to_string()))
+    }
+}
+
+/// Data to confirm a connection
+#[derive(
+    Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct ConnectionOpenConfirmData {
+    conn_id: String,
+    proof_height: (u64, u64),
+    proof_ack: EncodedProof,
+}
+
+impl ConnectionOpenConfirmData {
+    /// Returns the data to confirm a connection
+    pub fn new(
+        conn_id: ConnectionId,
+        proof_height: Height,
+        proof_ack: CommitmentProofBytes,
+    ) -> Self {
+        let conn_id = conn_id.as_str().to_owned();
+        Self {
+            conn_id,
+            proof_height: (
+                proof_height.revision_number,
+                proof_height.revision_height,
+            ),
+            proof_ack: EncodedProof::new(proof_ack),
+        }
+    }
+
+    /// Returns the connection ID
+    pub fn connection_id(&self) -> Option<ConnectionId> {
+        ConnectionId::from_str(&self.conn_id).ok()
+    }
+
+    /// Returns the height of the proof
+    pub fn proof_height(&self) -> Height {
+        Height::new(self.proof_height.0, self.proof_height.1)
+    }
+
+    /// Returns the proof for the connection in the ack state
+    pub fn proof_ack(&self) -> Result<CommitmentProofBytes> {
+        self.proof_ack.decode()
+    }
+
+    /// Returns the proofs
+    pub fn proofs(&self) -> Result<Proofs> {
+        let height = self.proof_height();
+        Proofs::new(self.proof_ack()?, None, None, None, height)
+            .map_err(|e| Error::DecodingError(e.to_string()))
+    }
+}
+
+/// Data to initialize a channel
+#[derive(
+    Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct ChannelOpenInitData {
+    port_id: String,
+    channel: EncodedChannelEnd,
+}
+
+impl ChannelOpenInitData {
+    /// Returns the data to initialize a channel
+    pub fn new(port_id: PortId, channel: ChannelEnd) -> Self {
+        let port_id = port_id.as_str().to_owned();
+        let channel = EncodedChannelEnd::new(&channel);
+        Self { port_id, channel }
+    }
+
+    /// Returns the port ID
+    pub fn port_id(&self) -> Option<PortId> {
+        PortId::from_str(&self.port_id).ok()
+    }
+
+    /// Returns the channel end
+    pub fn channel(&self) -> Result<ChannelEnd> {
+        self.channel.decode()
+    }
+}
+
+/// Data to try to open a channel
+#[derive(
+    Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct ChannelOpenTryData {
+    prev_channel_id: Option<String>,
+    port_id: String,
+    channel: EncodedChannelEnd,
+    counterparty_version: String,
+    proof_height: (u64, u64),
+    proof_channel: EncodedProof,
+}
+
+impl ChannelOpenTryData {
+    /// Returns the data to try to open a channel
+    pub fn new(
+        prev_channel_id: Option<ChannelId>,
+        port_id: PortId,
+        channel: ChannelEnd,
+        counterparty_version: ChannelVersion,
+        proof_height: Height,
+        proof_channel: CommitmentProofBytes,
+    ) -> Self {
+        let prev_channel_id =
+            prev_channel_id.map(|id| id.as_str().to_owned());
+        let port_id = port_id.as_str().to_owned();
+        let channel = EncodedChannelEnd::new(&channel);
+        Self {
+            prev_channel_id,
+            port_id,
+            channel,
+            counterparty_version: counterparty_version.to_string(),
+            proof_height: (
+                proof_height.revision_number,
+                proof_height.revision_height,
+            ),
+            proof_channel: EncodedProof::new(proof_channel),
+        }
+    }
+
+    /// Returns the previous channel ID
+    pub fn previous_channel_id(&self) -> Option<ChannelId> {
+        match &self.prev_channel_id {
+            Some(id) => ChannelId::from_str(id).ok(),
+            None => None,
+        }
+    }
+
+    /// Returns the port ID
+    pub fn port_id(&self) -> Option<PortId> {
+        PortId::from_str(&self.port_id).ok()
+    }
+
+    /// Returns the channel end
+    pub fn channel(&self) -> Result<ChannelEnd> {
+        self.channel.decode()
+    }
+
+    /// Returns the counterparty version
+    pub fn counterparty_version(&self) -> ChannelVersion {
+        ChannelVersion::from(self.counterparty_version.clone())
+    }
+
+    /// Returns the height of the proof
+    pub fn proof_height(&self) -> Height {
+        Height::new(self.proof_height.0, self.proof_height.1)
+    }
+
+    /// Returns the proof for the channel
+    pub fn proof_channel(&self) -> Result<CommitmentProofBytes> {
+        self.proof_channel.decode()
+    }
+
+    /// Returns the proofs
+    pub fn proofs(&self) -> Result<Proofs> {
+        let height = self.proof_height();
+        Proofs::new(self.proof_channel()?, None, None, None, height)
+            .map_err(|e| Error::DecodingError(e.to_string()))
+    }
+}
+
+/// Data to acknowledge a channel
+#[derive(
+    Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct ChannelOpenAckData {
+    port_id: String,
+    channel_id: String,
+    counterparty_channel_id: String,
+    counterparty_version: String,
+    proof_height: (u64, u64),
+    proof_channel: EncodedProof,
+}
+
+impl ChannelOpenAckData {
+    /// Returns the data to acknowledge a channel
+    pub fn new(
+        port_id: PortId,
+        channel_id: ChannelId,
+        counterparty_channel_id: ChannelId,
+        counterparty_version: ChannelVersion,
+        proof_height: Height,
+        proof_channel: CommitmentProofBytes,
+    ) -> Self {
+        Self {
+            port_id: port_id.as_str().to_owned(),
+            channel_id: channel_id.as_str().to_owned(),
+            counterparty_channel_id: counterparty_channel_id
+                .as_str()
+                .to_owned(),
+            counterparty_version: counterparty_version.to_string(),
+            proof_height: (
+                proof_height.revision_number,
+                proof_height.revision_height,
+            ),
+            proof_channel: EncodedProof::new(proof_channel),
+        }
+    }
+
+    /// Returns the port ID
+    pub fn port_id(&self) -> Option<PortId> {
+        PortId::from_str(&self.port_id).ok()
+    }
+
+    /// Returns the channel ID
+    pub fn channel_id(&self) -> Option<ChannelId> {
+        ChannelId::from_str(&self.channel_id).ok()
+    }
+
+    /// Returns the counterparty channel ID
+    pub fn counterparty_channel_id(&self) -> Option<ChannelId> {
+        ChannelId::from_str(&self.counterparty_channel_id).ok()
+    }
+
+    /// Returns the counterparty version
+    pub fn counterparty_version(&self) -> ChannelVersion {
+        ChannelVersion::from(self.counterparty_version.clone())
+    }
+
+    /// Returns the height of the proof
+    pub fn proof_height(&self) -> Height {
+        Height::new(self.proof_height.0, self.proof_height.1)
+    }
+
+    /// Returns the proof for the channel in the try state
+    pub fn proof_channel(&self) -> Result<CommitmentProofBytes> {
+        self.proof_channel.decode()
+    }
+
+    /// Returns the proofs
+    pub fn proofs(&self) -> Result<Proofs> {
+        let height = self.proof_height();
+        Proofs::new(self.proof_channel()?, None, None, None, height)
+            .map_err(|e| Error::DecodingError(e.to_string()))
+    }
+}
+
+/// Data to confirm a channel
+#[derive(
+    Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct ChannelOpenConfirmData {
+    port_id: String,
+    channel_id: String,
+    proof_height: (u64, u64),
+    proof_channel: EncodedProof,
+}
+
+impl ChannelOpenConfirmData {
+    /// Returns the data to confirm a channel
+    pub fn new(
+        port_id: PortId,
+        channel_id: ChannelId,
+        proof_height: Height,
+        proof_channel: CommitmentProofBytes,
+    ) -> Self {
+        Self {
+            port_id: port_id.as_str().to_owned(),
+            channel_id: channel_id.as_str().to_owned(),
+            proof_height: (
+                proof_height.revision_number,
+                proof_height.revision_height,
+            ),
+            proof_channel: EncodedProof::new(proof_channel),
+        }
+    }
+
+    /// Returns the port ID
+    pub fn port_id(&self) -> Option<PortId> {
+        PortId::from_str(&self.port_id).ok()
+    }
+
+    /// Returns the channel ID
+    pub fn channel_id(&self) -> Option<ChannelId> {
+        ChannelId::from_str(&self.channel_id).ok()
+    }
+
+    /// Returns the height of the proof
+    pub fn proof_height(&self) -> Height {
+        Height::new(self.proof_height.0, self.proof_height.1)
+    }
+
+    /// Returns the proof for the channel in the ack state
+    pub fn proof_channel(&self) -> Result<CommitmentProofBytes> {
+        self.proof_channel.decode()
+    }
+
+    /// Returns the proofs
+    pub fn proofs(&self) -> Result<Proofs> {
+        let height = self.proof_height();
+        Proofs::new(self.proof_channel()?, None, None, None, height)
+            .map_err(|e| Error::DecodingError(e.to_string()))
+    }
+}
+
+/// Data to receive a packet
+#[derive(
+    Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct PacketRecvData {
+    packet: EncodedPacket,
+    proof_height: (u64, u64),
+    proof_commitment: EncodedProof,
+}
+
+impl PacketRecvData {
+    /// Returns the data to receive a packet
+    pub fn new(
+        packet: Packet,
+        proof_height: Height,
+        proof_commitment: CommitmentProofBytes,
+    ) -> Self {
+        let packet = EncodedPacket::new(&packet);
+        Self {
+            packet,
+            proof_height: (
+                proof_height.revision_number,
+                proof_height.revision_height,
+            ),
+            proof_commitment: EncodedProof::new(proof_commitment),
+        }
+    }
+
+    /// Returns the packet
+    pub fn packet(&self) -> Result<Packet> {
+        self.packet.decode()
+    }
+
+    /// Returns the height of the proof
+    pub fn proof_height(&self) -> Height {
+        Height::new(self.proof_height.0, self.proof_height.1)
+    }
+
+    /// Returns the proof of the packet commitment
+    pub fn proof_commitment(&self) -> Result<CommitmentProofBytes> {
+        self.proof_commitment.decode()
+    }
+
+    /// Returns the proofs for the packet
+    pub fn proofs(&self) -> Result<Proofs> {
+        let height = self.proof_height();
+        Proofs::new(self.proof_commitment()?, None, None, None, height)
+            .map_err(|e| Error::DecodingError(e.to_string()))
+    }
+}
+
+/// Data to acknowledge a packet
+#[derive(
+    Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct PacketAckData {
+    packet: EncodedPacket,
+    ack: Vec<u8>,
+    proof_height: (u64, u64),
+    proof_acked: EncodedProof,
+}
+
+impl PacketAckData {
+    /// Returns the data to acknowledge a packet
+    pub fn new(
+        packet: Packet,
+        ack: Vec<u8>,
+        proof_height: Height,
+        proof_acked: CommitmentProofBytes,
+    ) -> Self {
+        let packet = EncodedPacket::new(&packet);
+        Self {
+            packet,
+            ack,
+            proof_height: (
+                proof_height.revision_number,
+                proof_height.revision_height,
+            ),
+            proof_acked: EncodedProof::new(proof_acked),
+        }
+    }
+
+    /// Returns the packet
+    pub fn packet(&self) -> Result<Packet> {
+        self.packet.decode()
+    }
+
+    /// Returns the acknowledgement bytes
+    pub fn acknowledgement(&self) -> Vec<u8> {
+        self.ack.clone()
+    }
+
+    /// Returns the height of the proof
+    pub fn proof_height(&self) -> Height {
+        Height::new(self.proof_height.0, self.proof_height.1)
+    }
+
+    /// Returns the proof of the acknowledgement
+    pub fn proof_acked(&self) -> Result<CommitmentProofBytes> {
+        self.proof_acked.decode()
+    }
+
+    /// Returns the proofs for the packet
+    pub fn proofs(&self) -> Result<Proofs> {
+        let height = self.proof_height();
+        Proofs::new(self.proof_acked()?, None, None, None, height)
+            .map_err(|e| Error::DecodingError(e.to_string()))
+    }
+}
+
+/// Data to time out a packet
+#[derive(
+    Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct PacketTimeoutData {
+    packet: EncodedPacket,
+    next_sequence_recv: u64,
+    proof_height: (u64, u64),
+    proof_unreceived: EncodedProof,
+}
+
+impl PacketTimeoutData {
+    /// Returns the data to time out a packet
+    pub fn new(
+        packet: Packet,
+        next_sequence_recv: Sequence,
+        proof_height: Height,
+        proof_unreceived: CommitmentProofBytes,
+    ) -> Self {
+        let packet = EncodedPacket::new(&packet);
+        Self {
+            packet,
+            next_sequence_recv: next_sequence_recv.into(),
+            proof_height: (
+                proof_height.revision_number,
+                proof_height.revision_height,
+            ),
+            proof_unreceived: EncodedProof::new(proof_unreceived),
+        }
+    }
+
+    /// Returns the packet
+    pub fn packet(&self) -> Result<Packet> {
+        self.packet.decode()
+    }
+
+    /// Returns the next sequence number expected by the receiving channel end
+    pub fn next_sequence_recv(&self) -> Sequence {
+        self.next_sequence_recv.into()
+    }
+
+    /// Returns the height of the proof
+    pub fn proof_height(&self) -> Height {
+        Height::new(self.proof_height.0, self.proof_height.1)
+    }
+
+    /// Returns the proof that the packet wasn't received
+    pub fn proof_unreceived(&self) -> Result<CommitmentProofBytes> {
+        self.proof_unreceived.decode()
+    }
+
+    /// Returns the proofs for the packet timeout
+    pub fn proofs(&self) -> Result<Proofs> {
+        let height = self.proof_height();
+        Proofs::new(self.proof_unreceived()?, None, None, None, height)
+            .map_err(|e| Error::DecodingError(e.to_string()))
+    }
+}
+
+/// The ICS20 fungible token packet data. [`Self::to_packet_bytes`] encodes
+/// it as the canonical JSON payload that counterparty chains expect in a
+/// `MsgTransfer` packet; the derived `Serialize` writes the fields in
+/// declaration order and is not that payload.
+#[derive(
+    Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct FungibleTokenPacketData {
+    denom: String,
+    amount: String,
+    sender: String,
+    receiver: String,
+}
+
+impl FungibleTokenPacketData {
+    /// Returns the fungible token packet data
+    pub fn new(
+        denom: String,
+        amount: String,
+        sender: String,
+        receiver: String,
+    ) -> Self {
+        Self {
+            denom,
+            amount,
+            sender,
+            receiver,
+        }
+    }
+
+    /// Returns the denomination of the token being transferred
+    pub fn denom(&self) -> &str {
+        &self.denom
+    }
+
+    /// Returns the amount of the token being transferred
+    pub fn amount(&self) -> &str {
+        &self.amount
+    }
+
+    /// Returns the sender address on the source chain
+    pub fn sender(&self) -> &str {
+        &self.sender
+    }
+
+    /// Returns the receiver address on the destination chain
+    pub fn receiver(&self) -> &str {
+        &self.receiver
+    }
+
+    /// Encodes this packet data as the canonical JSON bytes a counterparty
+    /// chain expects in a packet's `data` field. Cosmos SDK chains produce
+    /// this payload via `sdk.MustSortJSON`, i.e. with object keys sorted
+    /// alphabetically rather than in declaration order, so the fields are
+    /// written out through a sorted map instead of deriving `Serialize`.
+    pub fn to_packet_bytes(&self) -> Vec<u8> {
+        let mut sorted = std::collections::BTreeMap::new();
+        sorted.insert("amount", self.amount.as_str());
+        sorted.insert("denom", self.denom.as_str());
+        sorted.insert("receiver", self.receiver.as_str());
+        sorted.insert("sender", self.sender.as_str());
+        serde_json::to_vec(&sorted)
+            .expect("Encoding a fungible token packet shouldn't fail")
+    }
+}
+
+/// Data to send an ICS20 fungible token transfer
+#[derive(
+    Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct TransferData {
+    source_port: String,
+    source_channel: String,
+    token: FungibleTokenPacketData,
+    timeout_height: (u64, u64),
+    timeout_timestamp: u64,
+}
+
+impl TransferData {
+    /// Returns the data for an ICS20 fungible token transfer
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        source_port: PortId,
+        source_channel: ChannelId,
+        denom: String,
+        amount: String,
+        sender: String,
+        receiver: String,
+        timeout_height: Height,
+        timeout_timestamp: Timestamp,
+    ) -> Self {
+        Self {
+            source_port: source_port.as_str().to_owned(),
+            source_channel: source_channel.as_str().to_owned(),
+            token: FungibleTokenPacketData::new(
+                denom, amount, sender, receiver,
+            ),
+            timeout_height: (
+                timeout_height.revision_number,
+                timeout_height.revision_height,
+            ),
+            timeout_timestamp: timeout_timestamp.nanoseconds(),
+        }
+    }
+
+    /// Returns the source port ID
+    pub fn source_port(&self) -> Option<PortId> {
+        PortId::from_str(&self.source_port).ok()
+    }
+
+    /// Returns the source channel ID
+    pub fn source_channel(&self) -> Option<ChannelId> {
+        ChannelId::from_str(&self.source_channel).ok()
+    }
+
+    /// Returns the token being transferred
+    pub fn token(&self) -> &FungibleTokenPacketData {
+        &self.token
+    }
+
+    /// Returns the timeout height
+    pub fn timeout_height(&self) -> Height {
+        Height::new(self.timeout_height.0, self.timeout_height.1)
+    }
+
+    /// Returns the timeout timestamp
+    pub fn timeout_timestamp(&self) -> Timestamp {
+        Timestamp::from_nanoseconds(self.timeout_timestamp)
+            .expect("Decoding a timeout timestamp shouldn't fail")
+    }
+
+    /// Builds the outgoing packet bytes for this transfer
+    pub fn packet_data(&self) -> Vec<u8> {
+        self.token.to_packet_bytes()
+    }
+}
+
+/// Verifies that `value` is committed under `path` in the Merkle tree
+/// rooted at `root`. `proof` is expected to hold one `ExistenceProof` per
+/// level of `specs`/`path`, ordered from the innermost store (e.g. the
+/// IAVL tree of a module) to the outermost one (the Tendermint app hash):
+/// each level's computed subroot becomes the expected value of the next
+/// level up, and the final subroot must equal `root`.
+pub fn verify_membership(
+    proof: &MerkleProof,
+    specs: &ProofSpecs,
+    root: &CommitmentRoot,
+    path: &[Vec<u8>],
+    value: Vec<u8>,
+) -> Result<()> {
+    let specs = specs.as_slice();
+    if proof.proofs.is_empty()
+        || proof.proofs.len() != specs.len()
+        || proof.proofs.len() != path.len()
+    {
+        return Err(Error::ProofVerificationFailure(
+            "the number of proof layers doesn't match the proof specs or \
+             the key path"
+                .to_owned(),
+        ));
+    }
+
+    let mut expected_value = value;
+    let mut subroot = vec![];
+    for (i, (commitment_proof, spec)) in
+        proof.proofs.iter().zip(specs.iter()).enumerate()
+    {
+        let existence_proof = as_existence_proof(commitment_proof)?;
+        subroot =
+            verify_existence(spec, existence_proof, &path[i], &expected_value)?;
+        expected_value = subroot.clone();
+    }
+
+    if subroot != root.as_bytes() {
+        return Err(Error::ProofVerificationFailure(
+            "the computed root doesn't match the given commitment root"
+                .to_owned(),
+        ));
+    }
+    Ok(())
+}
+
+/// Verifies that `path` is absent from the Merkle tree rooted at `root`.
+/// The innermost proof is expected to be a `NonExistenceProof` bracketing
+/// the absent key with its left and right neighbours; the remaining
+/// (outer) levels are ordinary existence proofs of the resulting subroot,
+/// exactly as in [`verify_membership`].
+pub fn verify_non_membership(
+    proof: &MerkleProof,
+    specs: &ProofSpecs,
+    root: &CommitmentRoot,
+    path: &[Vec<u8>],
+) -> Result<()> {
+    let specs = specs.as_slice();
+    if proof.proofs.is_empty()
+        || proof.proofs.len() != specs.len()
+        || proof.proofs.len() != path.len()
+    {
+        return Err(Error::ProofVerificationFailure(
+            "the number of proof layers doesn't match the proof specs or \
+             the key path"
+                .to_owned(),
+        ));
+    }
+
+    let non_existence_proof = as_non_existence_proof(&proof.proofs[0])?;
+    let mut subroot =
+        verify_absence(&specs[0], non_existence_proof, &path[0])?;
+
+    let mut expected_value = subroot.clone();
+    for (i, (commitment_proof, spec)) in
+        proof.proofs.iter().zip(specs.iter()).enumerate().skip(1)
+    {
+        let existence_proof = as_existence_proof(commitment_proof)?;
+        subroot =
+            verify_existence(spec, existence_proof, &path[i], &expected_value)?;
+        expected_value = subroot.clone();
+    }
+
+    if subroot != root.as_bytes() {
+        return Err(Error::ProofVerificationFailure(
+            "the computed root doesn't match the given commitment root"
+                .to_owned(),
+        ));
+    }
+    Ok(())
+}
+
+/// Checks `proof` against `key` and `value` and returns the subroot it
+/// folds up to, without comparing that subroot to a commitment root (the
+/// caller does that once the whole path has been folded).
+fn verify_existence(
+    spec: &ProofSpec,
+    proof: &ExistenceProof,
+    key: &[u8],
+    value: &[u8],
+) -> Result<Vec<u8>> {
+    if proof.key != key {
+        return Err(Error::ProofVerificationFailure(
+            "the proof's key doesn't match the expected key".to_owned(),
+        ));
+    }
+    if proof.value != value {
+        return Err(Error::ProofVerificationFailure(
+            "the proof's value doesn't match the expected value".to_owned(),
+        ));
+    }
+    let leaf_spec = spec.leaf_spec.as_ref().ok_or_else(|| {
+        Error::ProofVerificationFailure(
+            "the proof spec is missing a leaf spec".to_owned(),
+        )
+    })?;
+    let inner_spec = spec.inner_spec.as_ref().ok_or_else(|| {
+        Error::ProofVerificationFailure(
+            "the proof spec is missing an inner spec".to_owned(),
+        )
+    })?;
+
+    let depth = proof.path.len() as i32;
+    if spec.min_depth > 0 && depth < spec.min_depth {
+        return Err(Error::ProofVerificationFailure(
+            "the proof's path is shallower than the proof spec allows"
+                .to_owned(),
+        ));
+    }
+    if spec.max_depth > 0 && depth > spec.max_depth {
+        return Err(Error::ProofVerificationFailure(
+            "the proof's path is deeper than the proof spec allows"
+                .to_owned(),
+        ));
+    }
+
+    let mut computed = leaf_hash(leaf_spec, &proof.key, &proof.value)?;
+    for step in &proof.path {
+        let prefix_len = step.prefix.len() as i32;
+        if prefix_len < inner_spec.min_prefix_length
+            || prefix_len
+                > inner_spec.max_prefix_length + inner_spec.child_size
+        {
+            return Err(Error::ProofVerificationFailure(
+                "an inner op has a prefix of an invalid length".to_owned(),
+            ));
+        }
+        if step.hash != inner_spec.hash {
+            return Err(Error::ProofVerificationFailure(
+                "an inner op uses a different hash op than the proof spec"
+                    .to_owned(),
+            ));
+        }
+        computed = inner_hash(step, &computed)?;
+    }
+    Ok(computed)
+}
+
+/// Checks that `key` falls strictly between the left and right neighbour
+/// existence proofs of `proof` (either of which may be absent at the
+/// edges of the tree), that the two neighbours are adjacent in the tree,
+/// and returns the subroot they fold up to.
+fn verify_absence(
+    spec: &ProofSpec,
+    proof: &NonExistenceProof,
+    key: &[u8],
+) -> Result<Vec<u8>> {
+    let left = proof.left.as_ref();
+    let right = proof.right.as_ref();
+    if left.is_none() && right.is_none() {
+        return Err(Error::ProofVerificationFailure(
+            "a non-existence proof needs at least one neighbour".to_owned(),
+        ));
+    }
+    if let Some(left) = left {
+        if left.key.as_slice() >= key {
+            return Err(Error::ProofVerificationFailure(
+                "the left neighbour doesn't come before the absent key"
+                    .to_owned(),
+            ));
+        }
+    }
+    if let Some(right) = right {
+        if right.key.as_slice() <= key {
+            return Err(Error::ProofVerificationFailure(
+                "the right neighbour doesn't come after the absent key"
+                    .to_owned(),
+            ));
+        }
+    }
+    if let (Some(left), Some(right)) = (left, right) {
+        verify_adjacent(left, right)?;
+    }
+
+    let mut root = None;
+    for neighbour in [left, right].into_iter().flatten() {
+        let computed =
+            verify_existence(spec, neighbour, &neighbour.key, &neighbour.value)?;
+        match &root {
+            Some(r) if *r != computed => {
+                return Err(Error::ProofVerificationFailure(
+                    "the neighbours don't fold up to the same subroot"
+                        .to_owned(),
+                ));
+            }
+            _ => root = Some(computed),
+        }
+    }
+    Ok(root.expect("at least one neighbour was checked above"))
+}
+
+/// Checks that `left` and `right` are in-order adjacent in a binary
+/// Merkle tree, i.e. they share the same ancestors except at their
+/// deepest common parent, where `left` is that parent's last child and
+/// `right` is its first child: nothing could exist between them.
+fn verify_adjacent(left: &ExistenceProof, right: &ExistenceProof) -> Result<()> {
+    if left.path.len() != right.path.len() {
+        return Err(Error::ProofVerificationFailure(
+            "the neighbouring proofs have a different depth".to_owned(),
+        ));
+    }
+    let depth = left.path.len();
+    if depth == 0 {
+        return Ok(());
+    }
+    for i in 0..depth - 1 {
+        if left.path[i].prefix != right.path[i].prefix
+            || left.path[i].suffix != right.path[i].suffix
+        {
+            return Err(Error::ProofVerificationFailure(
+                "the neighbouring proofs diverge above their common parent"
+                    .to_owned(),
+            ));
+        }
+    }
+    if left.path[depth - 1].suffix.is_empty() {
+        return Err(Error::ProofVerificationFailure(
+            "the left neighbour isn't the final child of its parent"
+                .to_owned(),
+        ));
+    }
+    if !right.path[depth - 1].prefix.is_empty() {
+        return Err(Error::ProofVerificationFailure(
+            "the right neighbour isn't the first child of its parent"
+                .to_owned(),
+        ));
+    }
+    Ok(())
+}
+
+/// Computes an ICS23 leaf hash: `hash(prefix || len(key') || key' ||
+/// len(value') || value')`, where `key'`/`value'` are `key`/`value` run
+/// through the leaf spec's `prehash_key`/`prehash_value` op first (e.g.
+/// `ics23::iavl_spec()`, used by real Cosmos IAVL proofs, sets
+/// `prehash_value: Sha256`).
+fn leaf_hash(spec: &LeafOp, key: &[u8], value: &[u8]) -> Result<Vec<u8>> {
+    let key = hash_op(spec.prehash_key, key)?;
+    let value = hash_op(spec.prehash_value, value)?;
+    let mut data = spec.prefix.clone();
+    data.extend(encode_length(spec.length, &key)?);
+    data.extend_from_slice(&key);
+    data.extend(encode_length(spec.length, &value)?);
+    data.extend_from_slice(&value);
+    hash_op(spec.hash, &data)
+}
+
+/// Computes an ICS23 inner-node hash: `hash(prefix || child || suffix)`.
+fn inner_hash(op: &InnerOp, child: &[u8]) -> Result<Vec<u8>> {
+    let mut data = op.prefix.clone();
+    data.extend_from_slice(child);
+    data.extend_from_slice(&op.suffix);
+    hash_op(op.hash, &data)
+}
+
+fn hash_op(op: i32, data: &[u8]) -> Result<Vec<u8>> {
+    match HashOp::from_i32(op) {
+        Some(HashOp::Sha256) => Ok(Sha256::digest(data).to_vec()),
+        Some(HashOp::NoHash) => Ok(data.to_vec()),
+        _ => Err(Error::ProofVerificationFailure(
+            "the proof spec uses an unsupported hash op".to_owned(),
+        )),
+    }
+}
+
+fn encode_length(op: i32, data: &[u8]) -> Result<Vec<u8>> {
+    match LengthOp::from_i32(op) {
+        Some(LengthOp::VarProto) => {
+            let mut buf = Vec::new();
+            prost::encoding::encode_varint(data.len() as u64, &mut buf);
+            Ok(buf)
+        }
+        Some(LengthOp::NoPrefix) => Ok(vec![]),
+        _ => Err(Error::ProofVerificationFailure(
+            "the proof spec uses an unsupported length op".to_owned(),
+        )),
+    }
+}
+
+fn as_existence_proof(proof: &CommitmentProof) -> Result<&ExistenceProof> {
+    match &proof.proof {
+        Some(Ics23Proof::Exist(ep)) => Ok(ep),
+        _ => Err(Error::ProofVerificationFailure(
+            "expected an existence proof at this level".to_owned(),
+        )),
+    }
+}
+
+fn as_non_existence_proof(
+    proof: &CommitmentProof,
+) -> Result<&NonExistenceProof> {
+    match &proof.proof {
+        Some(Ics23Proof::Nonexist(nep)) => Ok(nep),
+        _ => Err(Error::ProofVerificationFailure(
+            "expected a non-existence proof at this level".to_owned(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use ed25519_dalek::{Keypair, SecretKey, Signer};
+    use ibc::ics04_channel::channel::{
+        Counterparty as ChannelCounterparty, Order, State as ChannelState,
+    };
+    use ibc::ics23_commitment::commitment::CommitmentPrefix;
+    use ics23::InnerSpec;
+
+    use super::*;
+
+    /// A deterministic ed25519 keypair, distinguished by `seed`.
+    fn dummy_keypair(seed: u8) -> Keypair {
+        let secret = SecretKey::from_bytes(&[seed; 32]).unwrap();
+        let public = Ed25519PublicKey::from(&secret);
+        Keypair { secret, public }
+    }
+
+    fn dummy_solo_machine_client_state(
+        keypair: &Keypair,
+        sequence: u64,
+        is_frozen: bool,
+    ) -> SoloMachineClientState {
+        let consensus_state = SoloMachineConsensusState::new(
+            keypair.public.to_bytes().to_vec(),
+            "diversifier".to_owned(),
+            0,
+        );
+        SoloMachineClientState::new(sequence, is_frozen, consensus_state)
+    }
+
+    #[test]
+    fn verify_signature_rejects_a_frozen_client() {
+        let keypair = dummy_keypair(1);
+        let client_state = dummy_solo_machine_client_state(&keypair, 0, true);
+
+        let err = verify_signature(&client_state, 1, b"path", b"data", &[0u8; 64])
+            .unwrap_err();
+        assert!(matches!(err, Error::ProofVerificationFailure(_)));
+    }
+
+    #[test]
+    fn verify_signature_rejects_a_bad_signature() {
+        let keypair = dummy_keypair(1);
+        let client_state = dummy_solo_machine_client_state(&keypair, 0, false);
+
+        let sign_bytes = SignBytes {
+            sequence: 0,
+            timestamp: 1,
+            diversifier: "diversifier".to_owned(),
+            path: b"path".to_vec(),
+            data: b"data".to_vec(),
+        };
+        let mut bytes = vec![];
+        sign_bytes.encode(&mut bytes).unwrap();
+        // Signed by the wrong key.
+        let signature = dummy_keypair(2).sign(&bytes);
+
+        let err = verify_signature(
+            &client_state,
+            1,
+            b"path",
+            b"data",
+            &signature.to_bytes(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, Error::ProofVerificationFailure(_)));
+    }
+
+    #[test]
+    fn verify_signature_accepts_a_genuine_signature_and_bumps_the_sequence() {
+        let keypair = dummy_keypair(1);
+        let client_state = dummy_solo_machine_client_state(&keypair, 0, false);
+
+        let sign_bytes = SignBytes {
+            sequence: 0,
+            timestamp: 1,
+            diversifier: "diversifier".to_owned(),
+            path: b"path".to_vec(),
+            data: b"data".to_vec(),
+        };
+        let mut bytes = vec![];
+        sign_bytes.encode(&mut bytes).unwrap();
+        let signature = keypair.sign(&bytes);
+
+        let next = verify_signature(
+            &client_state,
+            1,
+            b"path",
+            b"data",
+            &signature.to_bytes(),
+        )
+        .unwrap();
+        assert_eq!(next.sequence(), 1);
+    }
+
+    #[test]
+    fn apply_header_rejects_a_frozen_client() {
+        let keypair = dummy_keypair(1);
+        let client_state = dummy_solo_machine_client_state(&keypair, 0, true);
+        let header = SoloMachineHeader::new(
+            0,
+            1,
+            vec![0u8; 32],
+            "new-diversifier".to_owned(),
+            vec![0u8; 64],
+        );
+
+        let err = apply_header(&client_state, &header).unwrap_err();
+        assert!(matches!(err, Error::ProofVerificationFailure(_)));
+    }
+
+    #[test]
+    fn apply_header_rejects_a_sequence_mismatch() {
+        let keypair = dummy_keypair(1);
+        let client_state = dummy_solo_machine_client_state(&keypair, 5, false);
+        // The client is at sequence 5, but the header rotates from 4.
+        let header = SoloMachineHeader::new(
+            4,
+            1,
+            vec![0u8; 32],
+            "new-diversifier".to_owned(),
+            vec![0u8; 64],
+        );
+
+        let err = apply_header(&client_state, &header).unwrap_err();
+        assert!(matches!(err, Error::ProofVerificationFailure(_)));
+    }
+
+    #[test]
+    fn apply_header_rejects_a_bad_signature() {
+        let keypair = dummy_keypair(1);
+        let client_state = dummy_solo_machine_client_state(&keypair, 0, false);
+        let new_public_key = dummy_keypair(3).public.to_bytes().to_vec();
+
+        let header_data = HeaderData {
+            new_public_key: new_public_key.clone(),
+            new_diversifier: "new-diversifier".to_owned(),
+        };
+        let mut data = vec![];
+        header_data.encode(&mut data).unwrap();
+        let sign_bytes = SignBytes {
+            sequence: 0,
+            timestamp: 1,
+            diversifier: "diversifier".to_owned(),
+            path: vec![],
+            data,
+        };
+        let mut bytes = vec![];
+        sign_bytes.encode(&mut bytes).unwrap();
+        // Signed by the wrong key, rather than the client's current one.
+        let signature = dummy_keypair(2).sign(&bytes);
+
+        let header = SoloMachineHeader::new(
+            0,
+            1,
+            new_public_key,
+            "new-diversifier".to_owned(),
+            signature.to_bytes().to_vec(),
+        );
+
+        let err = apply_header(&client_state, &header).unwrap_err();
+        assert!(matches!(err, Error::ProofVerificationFailure(_)));
+    }
+
+    #[test]
+    fn apply_header_accepts_a_genuine_rotation_and_bumps_the_sequence() {
+        let keypair = dummy_keypair(1);
+        let client_state = dummy_solo_machine_client_state(&keypair, 0, false);
+        let new_public_key = dummy_keypair(2).public.to_bytes().to_vec();
+
+        let header_data = HeaderData {
+            new_public_key: new_public_key.clone(),
+            new_diversifier: "new-diversifier".to_owned(),
+        };
+        let mut data = vec![];
+        header_data.encode(&mut data).unwrap();
+        let sign_bytes = SignBytes {
+            sequence: 0,
+            timestamp: 1,
+            diversifier: "diversifier".to_owned(),
+            path: vec![],
+            data,
+        };
+        let mut bytes = vec![];
+        sign_bytes.encode(&mut bytes).unwrap();
+        let signature = keypair.sign(&bytes);
+
+        let header = SoloMachineHeader::new(
+            0,
+            1,
+            new_public_key.clone(),
+            "new-diversifier".to_owned(),
+            signature.to_bytes().to_vec(),
+        );
+
+        let (next_client_state, next_consensus_state) =
+            apply_header(&client_state, &header).unwrap();
+        assert_eq!(next_client_state.sequence(), 1);
+        assert_eq!(
+            next_consensus_state.public_key(),
+            new_public_key.as_slice()
+        );
+        assert_eq!(next_consensus_state.diversifier(), "new-diversifier");
+    }
+
+    /// A leaf spec mirroring `ics23::iavl_spec()`: the value is SHA-256'd
+    /// before the length-prefixed concatenation, as real Cosmos IAVL
+    /// proofs require.
+    fn iavl_like_leaf_spec() -> LeafOp {
+        LeafOp {
+            hash: HashOp::Sha256 as i32,
+            prehash_key: HashOp::NoHash as i32,
+            prehash_value: HashOp::Sha256 as i32,
+            length: LengthOp::VarProto as i32,
+            prefix: vec![0],
+        }
+    }
+
+    fn dummy_inner_spec() -> InnerSpec {
+        InnerSpec {
+            child_order: vec![0, 1],
+            child_size: 33,
+            min_prefix_length: 4,
+            max_prefix_length: 12,
+            empty_child: vec![],
+            hash: HashOp::Sha256 as i32,
+        }
+    }
+
+    #[test]
+    fn leaf_hash_prehashes_the_value() {
+        let spec = iavl_like_leaf_spec();
+        let key = b"key".to_vec();
+        let value = b"value".to_vec();
+
+        let hashed_value = Sha256::digest(&value).to_vec();
+        let mut expected = spec.prefix.clone();
+        expected.extend(encode_length(spec.length, &key).unwrap());
+        expected.extend_from_slice(&key);
+        expected.extend(encode_length(spec.length, &hashed_value).unwrap());
+        expected.extend_from_slice(&hashed_value);
+        let expected = Sha256::digest(&expected).to_vec();
+
+        assert_eq!(leaf_hash(&spec, &key, &value).unwrap(), expected);
+        // Without the prehash step, the (wrong) hash would differ.
+        let mut unhashed = spec.prefix.clone();
+        unhashed.extend(encode_length(spec.length, &key).unwrap());
+        unhashed.extend_from_slice(&key);
+        unhashed.extend(encode_length(spec.length, &value).unwrap());
+        unhashed.extend_from_slice(&value);
+        let unhashed = Sha256::digest(&unhashed).to_vec();
+        assert_ne!(expected, unhashed);
+    }
+
+    fn single_level_existence_proof(
+        leaf_spec: &LeafOp,
+        key: &[u8],
+        value: &[u8],
+    ) -> (ProofSpec, ExistenceProof, Vec<u8>) {
+        let spec = ProofSpec {
+            leaf_spec: Some(leaf_spec.clone()),
+            inner_spec: Some(dummy_inner_spec()),
+            max_depth: 0,
+            min_depth: 0,
+        };
+        let root = leaf_hash(leaf_spec, key, value).unwrap();
+        let proof = ExistenceProof {
+            key: key.to_vec(),
+            value: value.to_vec(),
+            leaf: Some(leaf_spec.clone()),
+            path: vec![],
+        };
+        (spec, proof, root)
+    }
+
+    /// Builds an `ExistenceProof` whose path folds the leaf hash through
+    /// two real `InnerOp`s (a node and its parent), along with the
+    /// `ProofSpec` and root it verifies against.
+    fn two_level_existence_proof(
+        leaf_spec: &LeafOp,
+        inner_spec: &InnerSpec,
+        key: &[u8],
+        value: &[u8],
+    ) -> (ProofSpec, ExistenceProof, Vec<u8>) {
+        let spec = ProofSpec {
+            leaf_spec: Some(leaf_spec.clone()),
+            inner_spec: Some(inner_spec.clone()),
+            max_depth: 0,
+            min_depth: 0,
+        };
+        let leaf = leaf_hash(leaf_spec, key, value).unwrap();
+
+        let inner_op_1 = InnerOp {
+            hash: inner_spec.hash,
+            prefix: vec![0xaa; 4],
+            suffix: vec![0xbb; 32],
+        };
+        let node_1 = inner_hash(&inner_op_1, &leaf).unwrap();
+
+        let inner_op_2 = InnerOp {
+            hash: inner_spec.hash,
+            prefix: vec![0xcc; 4],
+            suffix: vec![0xdd; 32],
+        };
+        let root = inner_hash(&inner_op_2, &node_1).unwrap();
+
+        let proof = ExistenceProof {
+            key: key.to_vec(),
+            value: value.to_vec(),
+            leaf: Some(leaf_spec.clone()),
+            path: vec![inner_op_1, inner_op_2],
+        };
+        (spec, proof, root)
+    }
+
+    #[test]
+    fn verify_membership_accepts_a_genuine_iavl_style_leaf() {
+        let leaf_spec = iavl_like_leaf_spec();
+        let key = b"key".to_vec();
+        let value = b"value".to_vec();
+        let (spec, existence_proof, root) =
+            single_level_existence_proof(&leaf_spec, &key, &value);
+
+        let merkle_proof = MerkleProof {
+            proofs: vec![CommitmentProof {
+                proof: Some(Ics23Proof::Exist(existence_proof)),
+            }],
+        };
+
+        verify_membership(
+            &merkle_proof,
+            &ProofSpecs::from(vec![spec]),
+            &CommitmentRoot::from(root),
+            &[key],
+            value,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn verify_membership_rejects_a_tampered_value() {
+        let leaf_spec = iavl_like_leaf_spec();
+        let key = b"key".to_vec();
+        let value = b"value".to_vec();
+        let (spec, existence_proof, root) =
+            single_level_existence_proof(&leaf_spec, &key, &value);
+
+        let merkle_proof = MerkleProof {
+            proofs: vec![CommitmentProof {
+                proof: Some(Ics23Proof::Exist(existence_proof)),
+            }],
+        };
+
+        let tampered_value = b"not the value".to_vec();
+        assert!(verify_membership(
+            &merkle_proof,
+            &ProofSpecs::from(vec![spec]),
+            &CommitmentRoot::from(root),
+            &[key],
+            tampered_value,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn verify_membership_accepts_a_two_level_iavl_and_tendermint_proof() {
+        let leaf_spec = iavl_like_leaf_spec();
+        let inner_spec = dummy_inner_spec();
+        let key = b"key".to_vec();
+        let value = b"value".to_vec();
+        let (spec, existence_proof, store_root) =
+            two_level_existence_proof(&leaf_spec, &inner_spec, &key, &value);
+        // The outer (app hash) level commits to the inner store's root.
+        let store_key = b"ibc".to_vec();
+        let (outer_spec, outer_proof, root) =
+            single_level_existence_proof(&leaf_spec, &store_key, &store_root);
+
+        let merkle_proof = MerkleProof {
+            proofs: vec![
+                CommitmentProof {
+                    proof: Some(Ics23Proof::Exist(existence_proof)),
+                },
+                CommitmentProof {
+                    proof: Some(Ics23Proof::Exist(outer_proof)),
+                },
+            ],
+        };
+
+        verify_membership(
+            &merkle_proof,
+            &ProofSpecs::from(vec![spec, outer_spec]),
+            &CommitmentRoot::from(root),
+            &[key, store_key],
+            value,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn verify_membership_rejects_an_inner_op_with_a_mismatched_hash_op() {
+        let leaf_spec = iavl_like_leaf_spec();
+        let inner_spec = dummy_inner_spec();
+        let key = b"key".to_vec();
+        let value = b"value".to_vec();
+        let (spec, mut existence_proof, root) =
+            two_level_existence_proof(&leaf_spec, &inner_spec, &key, &value);
+        existence_proof.path[0].hash = HashOp::NoHash as i32;
+
+        let merkle_proof = MerkleProof {
+            proofs: vec![CommitmentProof {
+                proof: Some(Ics23Proof::Exist(existence_proof)),
+            }],
+        };
+
+        assert!(verify_membership(
+            &merkle_proof,
+            &ProofSpecs::from(vec![spec]),
+            &CommitmentRoot::from(root),
+            &[key],
+            value,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn verify_membership_rejects_a_proof_shallower_than_min_depth() {
+        let leaf_spec = iavl_like_leaf_spec();
+        let inner_spec = dummy_inner_spec();
+        let key = b"key".to_vec();
+        let value = b"value".to_vec();
+        let (mut spec, existence_proof, root) =
+            two_level_existence_proof(&leaf_spec, &inner_spec, &key, &value);
+        spec.min_depth = 3;
+
+        let merkle_proof = MerkleProof {
+            proofs: vec![CommitmentProof {
+                proof: Some(Ics23Proof::Exist(existence_proof)),
+            }],
+        };
+
+        assert!(verify_membership(
+            &merkle_proof,
+            &ProofSpecs::from(vec![spec]),
+            &CommitmentRoot::from(root),
+            &[key],
+            value,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn verify_membership_rejects_a_proof_deeper_than_max_depth() {
+        let leaf_spec = iavl_like_leaf_spec();
+        let inner_spec = dummy_inner_spec();
+        let key = b"key".to_vec();
+        let value = b"value".to_vec();
+        let (mut spec, existence_proof, root) =
+            two_level_existence_proof(&leaf_spec, &inner_spec, &key, &value);
+        spec.max_depth = 1;
+
+        let merkle_proof = MerkleProof {
+            proofs: vec![CommitmentProof {
+                proof: Some(Ics23Proof::Exist(existence_proof)),
+            }],
+        };
+
+        assert!(verify_membership(
+            &merkle_proof,
+            &ProofSpecs::from(vec![spec]),
+            &CommitmentRoot::from(root),
+            &[key],
+            value,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn verify_non_membership_accepts_a_bracketing_neighbour() {
+        let leaf_spec = iavl_like_leaf_spec();
+        let absent_key = b"bbb".to_vec();
+        let neighbour_key = b"ccc".to_vec();
+        let neighbour_value = b"value".to_vec();
+        let (spec, neighbour_proof, root) = single_level_existence_proof(
+            &leaf_spec,
+            &neighbour_key,
+            &neighbour_value,
+        );
+
+        let merkle_proof = MerkleProof {
+            proofs: vec![CommitmentProof {
+                proof: Some(Ics23Proof::Nonexist(NonExistenceProof {
+                    key: absent_key.clone(),
+                    left: None,
+                    right: Some(neighbour_proof),
+                })),
+            }],
+        };
+
+        verify_non_membership(
+            &merkle_proof,
+            &ProofSpecs::from(vec![spec]),
+            &CommitmentRoot::from(root),
+            &[absent_key],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn sync_committee_domain_depends_on_genesis_validators_root() {
+        let fork_version = [0u8; 4];
+        let domain_a =
+            compute_sync_committee_domain(&fork_version, &[1u8; 32]).unwrap();
+        let domain_b =
+            compute_sync_committee_domain(&fork_version, &[2u8; 32]).unwrap();
+
+        assert_eq!(&domain_a[..4], &SYNC_COMMITTEE_DOMAIN_TYPE);
+        assert_ne!(
+            domain_a, domain_b,
+            "the domain must distinguish networks/forks sharing the same \
+             fork version"
+        );
+    }
+
+    fn dummy_beacon_header() -> BeaconBlockHeader {
+        BeaconBlockHeader::new(0, 0, vec![0u8; 32], vec![0u8; 32], vec![0u8; 32])
+    }
+
+    #[test]
+    fn verify_update_rejects_a_frozen_client() {
+        let client_state = EthBeaconClientState::new(vec![0u8; 32], true);
+        let consensus_state = EthBeaconConsensusState::new(
+            dummy_beacon_header(),
+            vec![vec![0u8; BLS_PUBLIC_KEY_BYTES]; SYNC_COMMITTEE_SIZE],
+            vec![0u8; BLS_PUBLIC_KEY_BYTES],
+        );
+        let update = EthBeaconHeader::new(
+            dummy_beacon_header(),
+            vec![vec![0u8; BLS_PUBLIC_KEY_BYTES]; SYNC_COMMITTEE_SIZE],
+            vec![0u8; BLS_PUBLIC_KEY_BYTES],
+            vec![],
+            dummy_beacon_header(),
+            vec![],
+            SyncAggregate::new(vec![0u8; 64], vec![0u8; 96]),
+            vec![0u8; 4],
+        );
+
+        let err =
+            verify_update(&client_state, &consensus_state, &update).unwrap_err();
+        assert!(matches!(err, Error::ProofVerificationFailure(_)));
+    }
+
+    #[test]
+    fn ssz_merkleize_pads_with_zero_chunks_up_to_the_next_power_of_two() {
+        let chunk_a = [1u8; 32];
+        let chunk_b = [2u8; 32];
+        let chunk_c = [3u8; 32];
+
+        let root = ssz_merkleize(&[chunk_a, chunk_b, chunk_c], 3);
+
+        let left = sha256_pair(&chunk_a, &chunk_b);
+        let right = sha256_pair(&chunk_c, &[0u8; 32]);
+        let expected = sha256_pair(&left, &right);
+        assert_eq!(root, expected);
+    }
+
+    #[test]
+    fn verify_merkle_branch_accepts_a_known_good_branch_and_rejects_a_tampered_one()
+     {
+        let leaf = [9u8; 32];
+        let sibling = [1u8; 32];
+        let root = sha256_pair(&leaf, &sibling);
+
+        verify_merkle_branch(leaf, &[sibling.to_vec()], 2, root).unwrap();
+
+        let mut tampered_sibling = sibling;
+        tampered_sibling[0] ^= 0xff;
+        let err =
+            verify_merkle_branch(leaf, &[tampered_sibling.to_vec()], 2, root)
+                .unwrap_err();
+        assert!(matches!(err, Error::ProofVerificationFailure(_)));
+    }
+
+    #[test]
+    fn hash_tree_root_sync_committee_changes_when_a_pubkey_changes() {
+        let mut pubkeys =
+            vec![vec![1u8; BLS_PUBLIC_KEY_BYTES]; SYNC_COMMITTEE_SIZE];
+        let aggregate = vec![2u8; BLS_PUBLIC_KEY_BYTES];
+        let root_a =
+            hash_tree_root_sync_committee(&pubkeys, &aggregate).unwrap();
+
+        pubkeys[0] = vec![3u8; BLS_PUBLIC_KEY_BYTES];
+        let root_b =
+            hash_tree_root_sync_committee(&pubkeys, &aggregate).unwrap();
+
+        assert_ne!(root_a, root_b);
+    }
+
+    #[test]
+    fn hash_tree_root_sync_committee_rejects_the_wrong_committee_size() {
+        let pubkeys =
+            vec![vec![1u8; BLS_PUBLIC_KEY_BYTES]; SYNC_COMMITTEE_SIZE - 1];
+        let aggregate = vec![2u8; BLS_PUBLIC_KEY_BYTES];
+
+        let err = hash_tree_root_sync_committee(&pubkeys, &aggregate)
+            .unwrap_err();
+        assert!(matches!(err, Error::ProofVerificationFailure(_)));
+    }
+
+    /// Returns the (level, position-within-level) of a generalized index,
+    /// where level 0 is the root.
+    fn generalized_index_level(index: u64) -> (u32, u64) {
+        let level = 63 - index.leading_zeros();
+        (level, index - (1u64 << level))
+    }
+
+    /// Builds a full binary Merkle tree of `max_depth` levels below the
+    /// root, returning the node values at every level (`layers[0]` is the
+    /// root, `layers[max_depth]` are the leaves), with the nodes named by
+    /// `overrides` (generalized index, value) substituted in before their
+    /// ancestors are computed.
+    fn build_merkle_fixture(
+        max_depth: u32,
+        overrides: &[(u64, [u8; 32])],
+    ) -> Vec<Vec<[u8; 32]>> {
+        let apply_overrides =
+            |layer: &mut [[u8; 32]], depth: u32| {
+                for &(index, value) in overrides {
+                    let (level, pos) = generalized_index_level(index);
+                    if level == depth {
+                        layer[pos as usize] = value;
+                    }
+                }
+            };
+
+        let mut layers = vec![Vec::new(); max_depth as usize + 1];
+        let mut current: Vec<[u8; 32]> = (0..1u64 << max_depth)
+            .map(|i| {
+                let mut chunk = [0u8; 32];
+                chunk[..8].copy_from_slice(&i.to_le_bytes());
+                chunk
+            })
+            .collect();
+        apply_overrides(&mut current, max_depth);
+        layers[max_depth as usize] = current.clone();
+        for depth in (0..max_depth).rev() {
+            current = current
+                .chunks_exact(2)
+                .map(|pair| sha256_pair(&pair[0], &pair[1]))
+                .collect();
+            apply_overrides(&mut current, depth);
+            layers[depth as usize] = current.clone();
+        }
+        layers
+    }
+
+    /// Returns the Merkle branch proving the node at `index` against the
+    /// root of `layers`, as produced by [`build_merkle_fixture`].
+    fn branch_for(layers: &[Vec<[u8; 32]>], index: u64) -> Vec<Vec<u8>> {
+        let (mut level, mut pos) = generalized_index_level(index);
+        let mut branch = Vec::new();
+        while level >= 1 {
+            branch.push(layers[level as usize][(pos ^ 1) as usize].to_vec());
+            pos >>= 1;
+            level -= 1;
+        }
+        branch
+    }
+
+    #[test]
+    fn verify_update_accepts_a_well_formed_sync_committee_update() {
+        let genesis_validators_root = [7u8; 32];
+        let fork_version = [0u8; 4];
+        let client_state = EthBeaconClientState::new(
+            genesis_validators_root.to_vec(),
+            false,
+        );
+
+        let participant_count = SYNC_COMMITTEE_SIZE * 2 / 3 + 1;
+        let secret_keys: Vec<_> = (0..participant_count)
+            .map(|i| {
+                let mut seed = [0u8; 32];
+                seed[..8].copy_from_slice(&(i as u64).to_le_bytes());
+                blst::min_pk::SecretKey::key_gen(&seed, &[]).unwrap()
+            })
+            .collect();
+        let mut current_sync_committee: Vec<Vec<u8>> = secret_keys
+            .iter()
+            .map(|sk| sk.sk_to_pk().to_bytes().to_vec())
+            .collect();
+        current_sync_committee
+            .resize(SYNC_COMMITTEE_SIZE, vec![0u8; BLS_PUBLIC_KEY_BYTES]);
+        let current_sync_committee_aggregate = vec![0u8; BLS_PUBLIC_KEY_BYTES];
+
+        let mut sync_committee_bits = vec![0u8; SYNC_COMMITTEE_SIZE / 8];
+        for i in 0..participant_count {
+            sync_committee_bits[i / 8] |= 1u8 << (i % 8);
+        }
+
+        let consensus_state = EthBeaconConsensusState::new(
+            dummy_beacon_header(),
+            current_sync_committee,
+            current_sync_committee_aggregate,
+        );
+
+        let next_sync_committee =
+            vec![vec![0u8; BLS_PUBLIC_KEY_BYTES]; SYNC_COMMITTEE_SIZE];
+        let next_sync_committee_aggregate = vec![0u8; BLS_PUBLIC_KEY_BYTES];
+        let next_committee_root = hash_tree_root_sync_committee(
+            &next_sync_committee,
+            &next_sync_committee_aggregate,
+        )
+        .unwrap();
+
+        let finalized_header = dummy_beacon_header();
+        let finalized_root = finalized_header.hash_tree_root().unwrap();
+
+        let layers = build_merkle_fixture(6, &[
+            (NEXT_SYNC_COMMITTEE_GENERALIZED_INDEX, next_committee_root),
+            (FINALIZED_ROOT_GENERALIZED_INDEX, finalized_root),
+        ]);
+        let state_root = layers[0][0];
+        let next_sync_committee_branch =
+            branch_for(&layers, NEXT_SYNC_COMMITTEE_GENERALIZED_INDEX);
+        let finality_branch =
+            branch_for(&layers, FINALIZED_ROOT_GENERALIZED_INDEX);
+
+        let attested_header = BeaconBlockHeader::new(
+            0,
+            0,
+            vec![0u8; 32],
+            state_root.to_vec(),
+            vec![0u8; 32],
+        );
+        let attested_root = attested_header.hash_tree_root().unwrap();
+        let domain = compute_sync_committee_domain(
+            &fork_version,
+            &genesis_validators_root,
+        )
+        .unwrap();
+        let signing_root = sha256_pair(&attested_root, &domain);
+
+        let signatures: Vec<_> = secret_keys
+            .iter()
+            .map(|sk| sk.sign(&signing_root, BLS_DST, &[]))
+            .collect();
+        let signature_refs: Vec<_> = signatures.iter().collect();
+        let aggregate_signature = blst::min_pk::AggregateSignature::aggregate(
+            &signature_refs,
+            true,
+        )
+        .unwrap()
+        .to_signature();
+
+        let update = EthBeaconHeader::new(
+            attested_header,
+            next_sync_committee,
+            next_sync_committee_aggregate,
+            next_sync_committee_branch,
+            finalized_header,
+            finality_branch,
+            SyncAggregate::new(
+                sync_committee_bits,
+                aggregate_signature.to_bytes().to_vec(),
+            ),
+            fork_version.to_vec(),
+        );
+
+        let updated =
+            verify_update(&client_state, &consensus_state, &update).unwrap();
+        assert_eq!(
+            updated.current_sync_committee(),
+            consensus_state.current_sync_committee()
+        );
+    }
+
+    #[test]
+    fn bls_aggregate_verifies_a_genuine_aggregate_signature() {
+        let sk1 = blst::min_pk::SecretKey::key_gen(&[1u8; 32], &[]).unwrap();
+        let sk2 = blst::min_pk::SecretKey::key_gen(&[2u8; 32], &[]).unwrap();
+        let message = [7u8; 32];
+        let sig1 = sk1.sign(&message, BLS_DST, &[]);
+        let sig2 = sk2.sign(&message, BLS_DST, &[]);
+        let aggregate_signature =
+            blst::min_pk::AggregateSignature::aggregate(&[&sig1, &sig2], true)
+                .unwrap()
+                .to_signature();
+        let pubkeys = vec![
+            sk1.sk_to_pk().to_bytes().to_vec(),
+            sk2.sk_to_pk().to_bytes().to_vec(),
+        ];
+
+        verify_bls_aggregate(
+            &pubkeys,
+            &message,
+            &aggregate_signature.to_bytes(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn bls_aggregate_rejects_a_signature_over_a_different_message() {
+        let sk1 = blst::min_pk::SecretKey::key_gen(&[1u8; 32], &[]).unwrap();
+        let sk2 = blst::min_pk::SecretKey::key_gen(&[2u8; 32], &[]).unwrap();
+        let sig1 = sk1.sign(&[7u8; 32], BLS_DST, &[]);
+        let sig2 = sk2.sign(&[7u8; 32], BLS_DST, &[]);
+        let aggregate_signature =
+            blst::min_pk::AggregateSignature::aggregate(&[&sig1, &sig2], true)
+                .unwrap()
+                .to_signature();
+        let pubkeys = vec![
+            sk1.sk_to_pk().to_bytes().to_vec(),
+            sk2.sk_to_pk().to_bytes().to_vec(),
+        ];
+
+        assert!(verify_bls_aggregate(
+            &pubkeys,
+            &[8u8; 32],
+            &aggregate_signature.to_bytes(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn encoded_client_state_round_trips_valid_bytes() {
+        let client_state = AnyClientState::wasm(vec![1u8; 32], vec![2u8; 16]);
+        let bytes = client_state.encode_vec().unwrap();
+
+        let encoded = EncodedClientState::try_new(bytes.clone()).unwrap();
+        assert_eq!(encoded.decode().unwrap().encode_vec().unwrap(), bytes);
+    }
+
+    #[test]
+    fn encoded_client_state_rejects_garbage_bytes() {
+        let err = EncodedClientState::try_new(vec![0xff; 8]).unwrap_err();
+        assert!(matches!(err, Error::InvalidClientState(_)));
+    }
+
+    fn dummy_counterparty() -> Counterparty {
+        Counterparty::new(
+            ClientId::from_str("07-tendermint-0").unwrap(),
+            Some(ConnectionId::from_str("connection-0").unwrap()),
+            CommitmentPrefix::try_from(b"ibc".to_vec()).unwrap(),
+        )
+    }
+
+    #[test]
+    fn encoded_counterparty_round_trips_valid_bytes() {
+        let counterparty = dummy_counterparty();
+        let mut bytes = vec![];
+        RawCounterparty::from(counterparty).encode(&mut bytes).unwrap();
+
+        let encoded = EncodedCounterparty::try_new(bytes).unwrap();
+        assert_eq!(
+            encoded.decode().unwrap(),
+            dummy_counterparty()
+        );
+    }
+
+    #[test]
+    fn encoded_counterparty_rejects_garbage_bytes() {
+        let err = EncodedCounterparty::try_new(vec![0xff; 8]).unwrap_err();
+        assert!(matches!(err, Error::InvalidCounterparty(_)));
+    }
+
+    #[test]
+    fn encoded_version_round_trips_valid_bytes() {
+        let version = Version::default();
+        let bytes = version.encode_vec().unwrap();
+
+        let encoded = EncodedVersion::try_new(bytes.clone()).unwrap();
+        assert_eq!(encoded.decode().unwrap().encode_vec().unwrap(), bytes);
+    }
+
+    #[test]
+    fn encoded_version_rejects_garbage_bytes() {
+        let err = EncodedVersion::try_new(vec![0xff; 8]).unwrap_err();
+        assert!(matches!(err, Error::InvalidVersion(_)));
+    }
+
+    fn dummy_channel_end() -> ChannelEnd {
+        ChannelEnd::new(
+            ChannelState::Init,
+            Order::Unordered,
+            ChannelCounterparty::new(
+                PortId::from_str("transfer").unwrap(),
+                Some(ChannelId::from_str("channel-0").unwrap()),
+            ),
+            vec![ConnectionId::from_str("connection-0").unwrap()],
+            ChannelVersion::from("ics20-1".to_owned()),
+        )
+    }
+
+    #[test]
+    fn encoded_channel_end_round_trips_valid_bytes() {
+        let channel = dummy_channel_end();
+        let bytes = channel.encode_vec().unwrap();
+
+        let encoded = EncodedChannelEnd::try_new(bytes.clone()).unwrap();
+        assert_eq!(encoded.decode().unwrap().encode_vec().unwrap(), bytes);
+    }
+
+    #[test]
+    fn encoded_channel_end_rejects_garbage_bytes() {
+        let err = EncodedChannelEnd::try_new(vec![0xff; 8]).unwrap_err();
+        assert!(matches!(err, Error::InvalidChannel(_)));
+    }
+
+    fn dummy_packet() -> Packet {
+        Packet {
+            sequence: Sequence::from(1),
+            source_port: PortId::from_str("transfer").unwrap(),
+            source_channel: ChannelId::from_str("channel-0").unwrap(),
+            destination_port: PortId::from_str("transfer").unwrap(),
+            destination_channel: ChannelId::from_str("channel-1").unwrap(),
+            data: b"data".to_vec(),
+            timeout_height: Height::new(0, 10),
+            timeout_timestamp: Timestamp::none(),
+        }
+    }
+
+    #[test]
+    fn encoded_packet_round_trips_valid_bytes() {
+        let packet = dummy_packet();
+        let bytes = packet.encode_vec().unwrap();
+
+        let encoded = EncodedPacket::try_new(bytes.clone()).unwrap();
+        assert_eq!(encoded.decode().unwrap().encode_vec().unwrap(), bytes);
+    }
+
+    #[test]
+    fn encoded_packet_rejects_garbage_bytes() {
+        let err = EncodedPacket::try_new(vec![0xff; 8]).unwrap_err();
+        assert!(matches!(err, Error::InvalidPacket(_)));
+    }
+
+    fn dummy_merkle_proof_bytes() -> Vec<u8> {
+        let leaf_spec = iavl_like_leaf_spec();
+        let (_, existence_proof, _) =
+            single_level_existence_proof(&leaf_spec, b"key", b"value");
+        let merkle_proof = MerkleProof {
+            proofs: vec![CommitmentProof {
+                proof: Some(Ics23Proof::Exist(existence_proof)),
+            }],
+        };
+        let mut bytes = vec![];
+        merkle_proof.encode(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn encoded_proof_round_trips_valid_bytes() {
+        let bytes = dummy_merkle_proof_bytes();
+
+        let encoded = EncodedProof::try_new(bytes.clone()).unwrap();
+        let decoded: Vec<u8> = encoded.decode().unwrap().into();
+        assert_eq!(decoded, bytes);
+    }
+
+    #[test]
+    fn encoded_proof_rejects_garbage_bytes() {
+        let err = EncodedProof::try_new(vec![0xff; 8]).unwrap_err();
+        assert!(matches!(err, Error::InvalidProof(_)));
+    }
+
+    fn dummy_proof() -> CommitmentProofBytes {
+        dummy_merkle_proof_bytes().into()
+    }
+
+    #[test]
+    fn channel_open_try_data_round_trips() {
+        let channel = dummy_channel_end();
+        let data = ChannelOpenTryData::new(
+            Some(ChannelId::from_str("channel-0").unwrap()),
+            PortId::from_str("transfer").unwrap(),
+            channel.clone(),
+            ChannelVersion::from("ics20-1".to_owned()),
+            Height::new(0, 5),
+            dummy_proof(),
+        );
+
+        assert_eq!(
+            data.previous_channel_id(),
+            Some(ChannelId::from_str("channel-0").unwrap())
+        );
+        assert_eq!(
+            data.channel().unwrap().encode_vec().unwrap(),
+            channel.encode_vec().unwrap()
+        );
+        assert_eq!(data.proof_height(), Height::new(0, 5));
+        assert!(data.proofs().is_ok());
+    }
+
+    #[test]
+    fn channel_open_ack_data_round_trips() {
+        let data = ChannelOpenAckData::new(
+            PortId::from_str("transfer").unwrap(),
+            ChannelId::from_str("channel-0").unwrap(),
+            ChannelId::from_str("channel-1").unwrap(),
+            ChannelVersion::from("ics20-1".to_owned()),
+            Height::new(0, 5),
+            dummy_proof(),
+        );
+
+        assert_eq!(
+            data.channel_id(),
+            Some(ChannelId::from_str("channel-0").unwrap())
+        );
+        assert_eq!(
+            data.counterparty_channel_id(),
+            Some(ChannelId::from_str("channel-1").unwrap())
+        );
+        assert!(data.proofs().is_ok());
+    }
+
+    #[test]
+    fn packet_recv_data_round_trips() {
+        let packet = dummy_packet();
+        let data = PacketRecvData::new(
+            packet.clone(),
+            Height::new(0, 5),
+            dummy_proof(),
+        );
+
+        assert_eq!(
+            data.packet().unwrap().encode_vec().unwrap(),
+            packet.encode_vec().unwrap()
+        );
+        assert!(data.proofs().is_ok());
+    }
+
+    #[test]
+    fn packet_ack_data_round_trips() {
+        let packet = dummy_packet();
+        let data = PacketAckData::new(
+            packet.clone(),
+            b"ack".to_vec(),
+            Height::new(0, 5),
+            dummy_proof(),
+        );
+
+        assert_eq!(
+            data.packet().unwrap().encode_vec().unwrap(),
+            packet.encode_vec().unwrap()
+        );
+        assert_eq!(data.acknowledgement(), b"ack".to_vec());
+        assert!(data.proofs().is_ok());
+    }
+
+    #[test]
+    fn packet_timeout_data_round_trips() {
+        let packet = dummy_packet();
+        let data = PacketTimeoutData::new(
+            packet.clone(),
+            Sequence::from(7),
+            Height::new(0, 5),
+            dummy_proof(),
+        );
+
+        assert_eq!(
+            data.packet().unwrap().encode_vec().unwrap(),
+            packet.encode_vec().unwrap()
+        );
+        assert_eq!(data.next_sequence_recv(), Sequence::from(7));
+        assert!(data.proofs().is_ok());
+    }
+
+    #[test]
+    fn fungible_token_packet_data_serializes_keys_alphabetically() {
+        let data = FungibleTokenPacketData::new(
+            "samoleans".to_owned(),
+            "100".to_owned(),
+            "sender-addr".to_owned(),
+            "receiver-addr".to_owned(),
+        );
+
+        let bytes = data.to_packet_bytes();
+        let json = String::from_utf8(bytes).unwrap();
+        assert_eq!(
+            json,
+            r#"{"amount":"100","denom":"samoleans","receiver":"receiver-addr","sender":"sender-addr"}"#
+        );
+    }
+
+    #[test]
+    fn wrap_wasm_client_state_leaves_an_already_wrapped_state_unchanged() {
+        let client_state = AnyClientState::wasm(vec![1u8; 32], vec![4, 5, 6]);
+
+        let wrapped = wrap_wasm_client_state(&[9u8; 32], &client_state);
+
+        assert_eq!(
+            wrapped.encode_vec().unwrap(),
+            client_state.encode_vec().unwrap()
+        );
+    }
+
+    #[test]
+    fn unwrap_wasm_client_state_decodes_the_wrapped_data() {
+        let inner = AnyClientState::wasm(vec![9u8; 32], vec![1, 2, 3]);
+        let outer =
+            AnyClientState::wasm(vec![1u8; 32], inner.encode_vec().unwrap());
+
+        let unwrapped = unwrap_wasm_client_state(outer).unwrap();
+
+        assert_eq!(
+            unwrapped.encode_vec().unwrap(),
+            inner.encode_vec().unwrap()
+        );
+    }
+
+    #[test]
+    fn wrap_wasm_header_leaves_an_already_wrapped_header_unchanged() {
+        let header = AnyHeader::wasm(vec![1u8; 32], vec![4, 5, 6]);
+
+        let wrapped = wrap_wasm_header(&[9u8; 32], &header);
+
+        assert_eq!(
+            wrapped.encode_vec().unwrap(),
+            header.encode_vec().unwrap()
+        );
     }
 }